@@ -0,0 +1,202 @@
+//! Peak memory and time of `parse_sgml` on a synthetic submission
+//!
+//! The input mirrors `test_output/archive.tar`: a short primary document
+//! next to a multi-megabyte exhibit, plus a UU-encoded graphic. Peak heap
+//! use is measured with a counting global allocator and reported above
+//! the size of the input.
+//!
+//! Each filtered scenario is also run through the old path, which decoded
+//! every document and then cloned the kept ones out of the full list, and
+//! the run fails if the current path does not stay below it.
+//!
+//!     cargo bench --bench parse_memory
+
+use secsgmlrs::{parse_sgml, visit_sgml, DocumentFilter, DocumentMetadata, ParseOptions, SubmissionVisitor};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            if new_size > layout.size() {
+                let grown = new_size - layout.size();
+                let current = CURRENT.fetch_add(grown, Ordering::Relaxed) + grown;
+                PEAK.fetch_max(current, Ordering::Relaxed);
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: u32 = 20;
+
+/// About 2.5 MB: an 8-K with a 2 MB text exhibit and a 350 KB graphic
+fn synthetic_submission() -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(
+        b"<SEC-DOCUMENT>0000774352-01-000002.txt : 20010102\n\
+<SEC-HEADER>0000774352-01-000002.hdr.sgml : 20010102\n\
+ACCESSION NUMBER:\t\t0000774352-01-000002\n\
+CONFORMED SUBMISSION TYPE:\t8-K\n\
+PUBLIC DOCUMENT COUNT:\t\t3\n\
+FILED AS OF DATE:\t\t20010102\n\
+\n\
+FILER:\n\
+\n\
+\tCOMPANY DATA:\t\n\
+\t\tCOMPANY CONFORMED NAME:\t\t\tRESIDENTIAL FUNDING MORTGAGE SECURITIES I INC\n\
+\t\tCENTRAL INDEX KEY:\t\t\t0000774352\n\
+</SEC-HEADER>\n",
+    );
+
+    data.extend_from_slice(b"<DOCUMENT>\n<TYPE>8-K\n<SEQUENCE>1\n<FILENAME>0001.txt\n<TEXT>\n");
+    for i in 0..80 {
+        data.extend_from_slice(format!("Item 5. Other Events. Line {:04} of the current report.\n", i).as_bytes());
+    }
+    data.extend_from_slice(b"</TEXT>\n</DOCUMENT>\n");
+
+    data.extend_from_slice(b"<DOCUMENT>\n<TYPE>EX-99\n<SEQUENCE>2\n<FILENAME>0002.txt\n<TEXT>\n");
+    for i in 0..30_000 {
+        data.extend_from_slice(
+            format!("{:08}  LOAN  30YR FIXED   6.750%   $125,000.00   CA   OWNER OCCUPIED\n", i).as_bytes(),
+        );
+    }
+    data.extend_from_slice(b"</TEXT>\n</DOCUMENT>\n");
+
+    data.extend_from_slice(b"<DOCUMENT>\n<TYPE>GRAPHIC\n<SEQUENCE>3\n<FILENAME>chart.gif\n<TEXT>\nbegin 644 chart.gif\n");
+    // 45 bytes per line, all 0x00: `M` followed by 60 backticks
+    let line = format!("M{}\n", "`".repeat(60));
+    for _ in 0..5_600 {
+        data.extend_from_slice(line.as_bytes());
+    }
+    data.extend_from_slice(b"`\nend\n</TEXT>\n</DOCUMENT>\n</SEC-DOCUMENT>\n");
+
+    data
+}
+
+/// Sums content lengths, standing in for a writer or hasher
+struct ByteCounter(usize);
+
+impl SubmissionVisitor for ByteCounter {
+    fn on_document(&mut self, _metadata: &DocumentMetadata, content: &[u8]) -> ControlFlow<()> {
+        self.0 += content.len();
+        ControlFlow::Continue(())
+    }
+}
+
+fn measure(name: &str, data: &[u8], options: impl Fn() -> ParseOptions) -> usize {
+    measure_run(name, data, |data| {
+        let parsed = parse_sgml(data, options()).expect("synthetic submission parses");
+        std::hint::black_box(&parsed);
+    })
+}
+
+/// The path before documents were filtered ahead of decoding: parse
+/// everything, then clone the documents of the listed types
+fn measure_copying(name: &str, data: &[u8], types: &[&str]) -> usize {
+    measure_run(name, data, |data| {
+        let parsed = parse_sgml(data, ParseOptions::new()).expect("synthetic submission parses");
+        let kept: Vec<Vec<u8>> = parsed
+            .documents_with_metadata()
+            .filter(|(meta, _)| meta.doc_type().is_some_and(|t| types.contains(&t)))
+            .map(|(_, content)| content.to_vec())
+            .collect();
+        std::hint::black_box((&parsed, &kept));
+    })
+}
+
+fn measure_run(name: &str, data: &[u8], run: impl Fn(&[u8])) -> usize {
+    let mut peak = 0;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+
+        run(data);
+
+        peak = peak.max(PEAK.load(Ordering::Relaxed) - baseline);
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    println!(
+        "{:<34} {:>8.2} MB peak ({:>4.2}x input) {:>8.2} ms",
+        name,
+        peak as f64 / 1e6,
+        peak as f64 / data.len() as f64,
+        elapsed.as_secs_f64() * 1e3
+    );
+    peak
+}
+
+/// Fail the run when the current path is not below the copying one
+fn assert_below(current: usize, copying: usize) {
+    assert!(current < copying, "peak {} B is not below the copying path's {} B", current, copying);
+}
+
+fn main() {
+    let data = synthetic_submission();
+    println!("input: {:.2} MB, {} iterations", data.len() as f64 / 1e6, ITERATIONS);
+
+    let all_types = ["8-K", "EX-99", "GRAPHIC"];
+    let all = measure("all documents", &data, || {
+        ParseOptions::new().with_filter(all_types.iter().map(|t| t.to_string()).collect())
+    });
+    assert_below(all, measure_copying("all documents, copying", &data, &all_types));
+    // Decoded content is never larger than the input, so neither is the peak
+    assert!(all <= data.len(), "peak {} B exceeds the {} B input", all, data.len());
+
+    let primary = measure("primary only (type list)", &data, || {
+        ParseOptions::new().with_filter(vec!["8-K".to_string()])
+    });
+    assert_below(primary, measure_copying("primary only, copying", &data, &["8-K"]));
+    measure("primary only, keep metadata", &data, || {
+        let mut options = ParseOptions::new().with_filter(vec!["8-K".to_string()]);
+        options.keep_filtered_metadata = true;
+        options
+    });
+    measure("exclude large documents", &data, || {
+        ParseOptions::new().with_document_filter(DocumentFilter {
+            max_size: Some(100_000),
+            ..Default::default()
+        })
+    });
+
+    let graphic = measure("graphic only (decode)", &data, || {
+        ParseOptions::new().with_document_filter(DocumentFilter::types(["GRAPHIC"]))
+    });
+    assert_below(graphic, measure_copying("graphic only, copying", &data, &["GRAPHIC"]));
+
+    let streamed = measure_run("all documents, streamed", &data, |data| {
+        let mut counter = ByteCounter(0);
+        visit_sgml(data, ParseOptions::new(), &mut counter).expect("synthetic submission parses");
+        std::hint::black_box(counter.0);
+    });
+    assert_below(streamed, all);
+}
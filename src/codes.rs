@@ -0,0 +1,895 @@
+//! Embedded SEC SIC code and EDGAR state/country code tables
+//!
+//! Used by the optional enrichment step that adds descriptive fields next
+//! to `assigned-sic` and state codes in the header.
+
+use crate::header_mappings::{derived_key, standardize_key};
+use crate::types::MetadataValue;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A Standard Industrial Classification code and the SEC office reviewing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SicCode {
+    pub code: u16,
+    pub description: &'static str,
+    pub office: &'static str,
+}
+
+const ENERGY: &str = "Office of Energy & Transportation";
+const FINANCE: &str = "Office of Finance";
+const IAS: &str = "Industrial Applications and Services";
+const INTERNATIONAL: &str = "Office of International Corp Fin";
+const LIFE: &str = "Office of Life Sciences";
+const MANUFACTURING: &str = "Office of Manufacturing";
+const REAL_ESTATE: &str = "Office of Real Estate & Construction";
+const STRUCTURED: &str = "Office of Structured Finance";
+const TECHNOLOGY: &str = "Office of Technology";
+const TRADE: &str = "Office of Trade & Services";
+
+/// SIC codes sorted by code
+static SIC_CODES: &[(u16, &str, &str)] = &[
+    (100, "AGRICULTURAL PRODUCTION-CROPS", IAS),
+    (200, "AGRICULTURAL PROD-LIVESTOCK & ANIMAL SPECIALTIES", IAS),
+    (700, "AGRICULTURAL SERVICES", IAS),
+    (800, "FORESTRY", IAS),
+    (900, "FISHING, HUNTING AND TRAPPING", IAS),
+    (1000, "METAL MINING", ENERGY),
+    (1040, "GOLD AND SILVER ORES", ENERGY),
+    (1090, "MISCELLANEOUS METAL ORES", ENERGY),
+    (1220, "BITUMINOUS COAL & LIGNITE MINING", ENERGY),
+    (1221, "BITUMINOUS COAL & LIGNITE SURFACE MINING", ENERGY),
+    (1311, "CRUDE PETROLEUM & NATURAL GAS", ENERGY),
+    (1381, "DRILLING OIL & GAS WELLS", ENERGY),
+    (1382, "OIL & GAS FIELD EXPLORATION SERVICES", ENERGY),
+    (1389, "OIL & GAS FIELD SERVICES, NEC", ENERGY),
+    (1400, "MINING & QUARRYING OF NONMETALLIC MINERALS (NO FUELS)", ENERGY),
+    (1520, "GENERAL BLDG CONTRACTORS - RESIDENTIAL BLDGS", REAL_ESTATE),
+    (1531, "OPERATIVE BUILDERS", REAL_ESTATE),
+    (1540, "GENERAL BLDG CONTRACTORS - NONRESIDENTIAL BLDGS", REAL_ESTATE),
+    (1600, "HEAVY CONSTRUCTION OTHER THAN BLDG CONST - CONTRACTORS", REAL_ESTATE),
+    (1623, "WATER, SEWER, PIPELINE, COMM & POWER LINE CONSTRUCTION", REAL_ESTATE),
+    (1700, "CONSTRUCTION - SPECIAL TRADE CONTRACTORS", REAL_ESTATE),
+    (1731, "ELECTRICAL WORK", REAL_ESTATE),
+    (2000, "FOOD AND KINDRED PRODUCTS", MANUFACTURING),
+    (2011, "MEAT PACKING PLANTS", MANUFACTURING),
+    (2013, "SAUSAGES & OTHER PREPARED MEAT PRODUCTS", MANUFACTURING),
+    (2015, "POULTRY SLAUGHTERING AND PROCESSING", MANUFACTURING),
+    (2020, "DAIRY PRODUCTS", MANUFACTURING),
+    (2024, "ICE CREAM & FROZEN DESSERTS", MANUFACTURING),
+    (2030, "CANNED, FROZEN & PRESERVD FRUIT, VEG & FOOD SPECIALTIES", MANUFACTURING),
+    (2033, "CANNED, FRUITS, VEG, PRESERVES, JAMS & JELLIES", MANUFACTURING),
+    (2040, "GRAIN MILL PRODUCTS", MANUFACTURING),
+    (2050, "BAKERY PRODUCTS", MANUFACTURING),
+    (2052, "COOKIES & CRACKERS", MANUFACTURING),
+    (2060, "SUGAR & CONFECTIONERY PRODUCTS", MANUFACTURING),
+    (2070, "FATS & OILS", MANUFACTURING),
+    (2080, "BEVERAGES", MANUFACTURING),
+    (2082, "MALT BEVERAGES", MANUFACTURING),
+    (2086, "BOTTLED & CANNED SOFT DRINKS & CARBONATED WATERS", MANUFACTURING),
+    (2090, "MISCELLANEOUS FOOD PREPARATIONS & KINDRED PRODUCTS", MANUFACTURING),
+    (2092, "PREPARED FRESH OR FROZEN FISH & SEAFOODS", MANUFACTURING),
+    (2100, "TOBACCO PRODUCTS", MANUFACTURING),
+    (2111, "CIGARETTES", MANUFACTURING),
+    (2200, "TEXTILE MILL PRODUCTS", MANUFACTURING),
+    (2211, "BROADWOVEN FABRIC MILLS, COTTON", MANUFACTURING),
+    (2221, "BROADWOVEN FABRIC MILLS, MAN MADE FIBER & SILK", MANUFACTURING),
+    (2250, "KNITTING MILLS", MANUFACTURING),
+    (2253, "KNIT OUTERWEAR MILLS", MANUFACTURING),
+    (2273, "CARPETS & RUGS", MANUFACTURING),
+    (2300, "APPAREL & OTHER FINISHD PRODS OF FABRICS & SIMILAR MATL", MANUFACTURING),
+    (2320, "MEN'S & BOYS' FURNISHGS, WORK CLOTHG, & ALLIED GARMENTS", MANUFACTURING),
+    (2330, "WOMEN'S, MISSES', AND JUNIORS OUTERWEAR", MANUFACTURING),
+    (2340, "WOMEN'S, MISSES', CHILDREN'S & INFANTS' UNDERGARMENTS", MANUFACTURING),
+    (2390, "MISCELLANEOUS FABRICATED TEXTILE PRODUCTS", MANUFACTURING),
+    (2400, "LUMBER & WOOD PRODUCTS (NO FURNITURE)", MANUFACTURING),
+    (2421, "SAWMILLS & PLANTING MILLS, GENERAL", MANUFACTURING),
+    (2430, "MILLWOOD, VENEER, PLYWOOD, & STRUCTURAL WOOD MEMBERS", MANUFACTURING),
+    (2451, "MOBILE HOMES", MANUFACTURING),
+    (2452, "PREFABRICATED WOOD BLDGS & COMPONENTS", MANUFACTURING),
+    (2510, "HOUSEHOLD FURNITURE", MANUFACTURING),
+    (2511, "WOOD HOUSEHOLD FURNITURE, (NO UPHOLSTERED)", MANUFACTURING),
+    (2520, "OFFICE FURNITURE", MANUFACTURING),
+    (2522, "OFFICE FURNITURE (NO WOOD)", MANUFACTURING),
+    (2531, "PUBLIC BLDG & RELATED FURNITURE", MANUFACTURING),
+    (2540, "PARTITIONS, SHELVG, LOCKERS, & OFFICE & STORE FIXTURES", MANUFACTURING),
+    (2590, "MISCELLANEOUS FURNITURE & FIXTURES", MANUFACTURING),
+    (2600, "PAPERS & ALLIED PRODUCTS", MANUFACTURING),
+    (2611, "PULP MILLS", MANUFACTURING),
+    (2621, "PAPER MILLS", MANUFACTURING),
+    (2631, "PAPERBOARD MILLS", MANUFACTURING),
+    (2650, "PAPERBOARD CONTAINERS & BOXES", MANUFACTURING),
+    (2670, "CONVERTED PAPER & PAPERBOARD PRODS (NO CONTAINERS/BOXES)", MANUFACTURING),
+    (2673, "PLASTICS, FOIL & COATED PAPER BAGS", MANUFACTURING),
+    (2711, "NEWSPAPERS: PUBLISHING OR PUBLISHING & PRINTING", TRADE),
+    (2721, "PERIODICALS: PUBLISHING OR PUBLISHING & PRINTING", TRADE),
+    (2731, "BOOKS: PUBLISHING OR PUBLISHING & PRINTING", TRADE),
+    (2732, "BOOK PRINTING", TRADE),
+    (2741, "MISCELLANEOUS PUBLISHING", TRADE),
+    (2750, "COMMERCIAL PRINTING", TRADE),
+    (2761, "MANIFOLD BUSINESS FORMS", TRADE),
+    (2771, "GREETING CARDS", TRADE),
+    (2780, "BLANKBOOKS, LOOSELEAF BINDERS & BOOKBINDG & RELATD WORK", TRADE),
+    (2790, "SERVICE INDUSTRIES FOR THE PRINTING TRADE", TRADE),
+    (2800, "CHEMICALS & ALLIED PRODUCTS", IAS),
+    (2810, "INDUSTRIAL INORGANIC CHEMICALS", IAS),
+    (2820, "PLASTIC MATERIAL, SYNTH RESIN/RUBBER, CELLULOS (NO GLASS)", IAS),
+    (2821, "PLASTIC MATERIALS, SYNTH RESINS & NONVULCAN ELASTOMERS", IAS),
+    (2833, "MEDICINAL CHEMICALS & BOTANICAL PRODUCTS", LIFE),
+    (2834, "PHARMACEUTICAL PREPARATIONS", LIFE),
+    (2835, "IN VITRO & IN VIVO DIAGNOSTIC SUBSTANCES", LIFE),
+    (2836, "BIOLOGICAL PRODUCTS, (NO DIAGNOSTIC SUBSTANCES)", LIFE),
+    (2840, "SOAP, DETERGENTS, CLEANG PREPARATIONS, PERFUMES, COSMETICS", MANUFACTURING),
+    (2842, "SPECIALTY CLEANING, POLISHING AND SANITATION PREPARATIONS", MANUFACTURING),
+    (2844, "PERFUMES, COSMETICS & OTHER TOILET PREPARATIONS", MANUFACTURING),
+    (2851, "PAINTS, VARNISHES, LACQUERS, ENAMELS & ALLIED PRODS", IAS),
+    (2860, "INDUSTRIAL ORGANIC CHEMICALS", IAS),
+    (2870, "AGRICULTURAL CHEMICALS", IAS),
+    (2890, "MISCELLANEOUS CHEMICAL PRODUCTS", IAS),
+    (2891, "ADHESIVES & SEALANTS", IAS),
+    (2911, "PETROLEUM REFINING", ENERGY),
+    (2950, "ASPHALT PAVING & ROOFING MATERIALS", ENERGY),
+    (2990, "MISCELLANEOUS PRODUCTS OF PETROLEUM & COAL", ENERGY),
+    (3011, "TIRES & INNER TUBES", MANUFACTURING),
+    (3021, "RUBBER & PLASTICS FOOTWEAR", MANUFACTURING),
+    (3050, "GASKETS, PACKG & SEALG DEVICES & RUBBER & PLASTICS HOSE", MANUFACTURING),
+    (3060, "FABRICATED RUBBER PRODUCTS, NEC", MANUFACTURING),
+    (3080, "MISCELLANEOUS PLASTICS PRODUCTS", MANUFACTURING),
+    (3081, "UNSUPPORTED PLASTICS FILM & SHEET", MANUFACTURING),
+    (3086, "PLASTICS FOAM PRODUCTS", MANUFACTURING),
+    (3089, "PLASTICS PRODUCTS, NEC", MANUFACTURING),
+    (3100, "LEATHER & LEATHER PRODUCTS", MANUFACTURING),
+    (3140, "FOOTWEAR, (NO RUBBER)", MANUFACTURING),
+    (3211, "FLAT GLASS", MANUFACTURING),
+    (3220, "GLASS & GLASSWARE, PRESSED OR BLOWN", MANUFACTURING),
+    (3221, "GLASS CONTAINERS", MANUFACTURING),
+    (3231, "GLASS PRODUCTS, MADE OF PURCHASED GLASS", MANUFACTURING),
+    (3241, "CEMENT, HYDRAULIC", MANUFACTURING),
+    (3250, "STRUCTURAL CLAY PRODUCTS", MANUFACTURING),
+    (3260, "POTTERY & RELATED PRODUCTS", MANUFACTURING),
+    (3270, "CONCRETE, GYPSUM & PLASTER PRODUCTS", MANUFACTURING),
+    (3272, "CONCRETE PRODUCTS, EXCEPT BLOCK & BRICK", MANUFACTURING),
+    (3281, "CUT STONE & STONE PRODUCTS", MANUFACTURING),
+    (3290, "ABRASIVE, ASBESTOS & MISC NONMETALLIC MINERAL PRODS", MANUFACTURING),
+    (3310, "STEEL WORKS, BLAST FURNACES & ROLLING & FINISHING MILLS", MANUFACTURING),
+    (3312, "STEEL WORKS, BLAST FURNACES & ROLLING MILLS (COKE OVENS)", MANUFACTURING),
+    (3317, "STEEL PIPE & TUBES", MANUFACTURING),
+    (3320, "IRON & STEEL FOUNDRIES", MANUFACTURING),
+    (3330, "PRIMARY SMELTING & REFINING OF NONFERROUS METALS", MANUFACTURING),
+    (3334, "PRIMARY PRODUCTION OF ALUMINUM", MANUFACTURING),
+    (3341, "SECONDARY SMELTING & REFINING OF NONFERROUS METALS", MANUFACTURING),
+    (3350, "ROLLING DRAWING & EXTRUDING OF NONFERROUS METALS", MANUFACTURING),
+    (3357, "DRAWING & INSULATING OF NONFERROUS WIRE", MANUFACTURING),
+    (3360, "NONFERROUS FOUNDRIES (CASTINGS)", MANUFACTURING),
+    (3390, "MISCELLANEOUS PRIMARY METAL PRODUCTS", MANUFACTURING),
+    (3411, "METAL CANS", MANUFACTURING),
+    (3412, "METAL SHIPPING BARRELS, DRUMS, KEGS & PAILS", MANUFACTURING),
+    (3420, "CUTLERY, HANDTOOLS & GENERAL HARDWARE", MANUFACTURING),
+    (3430, "HEATING EQUIP, EXCEPT ELEC & WARM AIR; & PLUMBING FIXTURES", MANUFACTURING),
+    (3433, "HEATING EQUIPMENT, EXCEPT ELECTRIC & WARM AIR FURNACES", MANUFACTURING),
+    (3440, "FABRICATED STRUCTURAL METAL PRODUCTS", MANUFACTURING),
+    (3442, "METAL DOORS, SASH, FRAMES, MOLDINGS & TRIM", MANUFACTURING),
+    (3443, "FABRICATED PLATE WORK (BOILER SHOPS)", MANUFACTURING),
+    (3444, "SHEET METAL WORK", MANUFACTURING),
+    (3448, "PREFABRICATED METAL BUILDINGS & COMPONENTS", MANUFACTURING),
+    (3451, "SCREW MACHINE PRODUCTS", MANUFACTURING),
+    (3452, "BOLTS, NUTS, SCREWS, RIVETS & WASHERS", MANUFACTURING),
+    (3460, "METAL FORGINGS & STAMPINGS", MANUFACTURING),
+    (3470, "COATING, ENGRAVING & ALLIED SERVICES", MANUFACTURING),
+    (3480, "ORDNANCE & ACCESSORIES, (NO VEHICLES/GUIDED MISSILES)", MANUFACTURING),
+    (3490, "MISCELLANEOUS FABRICATED METAL PRODUCTS", MANUFACTURING),
+    (3510, "ENGINES & TURBINES", MANUFACTURING),
+    (3523, "FARM MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3524, "LAWN & GARDEN TRACTORS & HOME LAWN & GARDENS EQUIP", MANUFACTURING),
+    (3530, "CONSTRUCTION, MINING & MATERIALS HANDLING MACHINERY & EQUIP", MANUFACTURING),
+    (3531, "CONSTRUCTION MACHINERY & EQUIP", MANUFACTURING),
+    (3532, "MINING MACHINERY & EQUIP (NO OIL & GAS FIELD MACH & EQUIP)", MANUFACTURING),
+    (3533, "OIL & GAS FIELD MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3537, "INDUSTRIAL TRUCKS, TRACTORS, TRAILERS & STACKERS", MANUFACTURING),
+    (3540, "METALWORKG MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3541, "MACHINE TOOLS, METAL CUTTING TYPES", MANUFACTURING),
+    (3550, "SPECIAL INDUSTRY MACHINERY (NO METALWORKING MACHINERY)", MANUFACTURING),
+    (3555, "PRINTING TRADES MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3559, "SPECIAL INDUSTRY MACHINERY, NEC", MANUFACTURING),
+    (3560, "GENERAL INDUSTRIAL MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3561, "PUMPS & PUMPING EQUIPMENT", MANUFACTURING),
+    (3562, "BALL & ROLLER BEARINGS", MANUFACTURING),
+    (3564, "INDUSTRIAL & COMMERCIAL FANS & BLOWERS & AIR PURIFING EQUIP", MANUFACTURING),
+    (3567, "INDUSTRIAL PROCESS FURNACES & OVENS", MANUFACTURING),
+    (3569, "GENERAL INDUSTRIAL MACHINERY & EQUIPMENT, NEC", MANUFACTURING),
+    (3570, "COMPUTER & OFFICE EQUIPMENT", TECHNOLOGY),
+    (3571, "ELECTRONIC COMPUTERS", TECHNOLOGY),
+    (3572, "COMPUTER STORAGE DEVICES", TECHNOLOGY),
+    (3575, "COMPUTER TERMINALS", TECHNOLOGY),
+    (3576, "COMPUTER COMMUNICATIONS EQUIPMENT", TECHNOLOGY),
+    (3577, "COMPUTER PERIPHERAL EQUIPMENT, NEC", TECHNOLOGY),
+    (3578, "CALCULATING & ACCOUNTING MACHINES (NO ELECTRONIC COMPUTERS)", TECHNOLOGY),
+    (3579, "OFFICE MACHINES, NEC", TECHNOLOGY),
+    (3580, "REFRIGERATION & SERVICE INDUSTRY MACHINERY", MANUFACTURING),
+    (3585, "AIR-COND & WARM AIR HEATG EQUIP & COMM & INDL REFRIG EQUIP", MANUFACTURING),
+    (3590, "MISC INDUSTRIAL & COMMERCIAL MACHINERY & EQUIPMENT", MANUFACTURING),
+    (3600, "ELECTRONIC & OTHER ELECTRICAL EQUIPMENT (NO COMPUTER EQUIP)", MANUFACTURING),
+    (3612, "POWER, DISTRIBUTION & SPECIALTY TRANSFORMERS", MANUFACTURING),
+    (3613, "SWITCHGEAR & SWITCHBOARD APPARATUS", MANUFACTURING),
+    (3620, "ELECTRICAL INDUSTRIAL APPARATUS", MANUFACTURING),
+    (3621, "MOTORS & GENERATORS", MANUFACTURING),
+    (3630, "HOUSEHOLD APPLIANCES", MANUFACTURING),
+    (3634, "ELECTRIC HOUSEWARES & FANS", MANUFACTURING),
+    (3640, "ELECTRIC LIGHTING & WIRING EQUIPMENT", MANUFACTURING),
+    (3651, "HOUSEHOLD AUDIO & VIDEO EQUIPMENT", MANUFACTURING),
+    (3652, "PHONOGRAPH RECORDS & PRERECORDED AUDIO TAPES & DISKS", MANUFACTURING),
+    (3660, "COMMUNICATIONS EQUIPMENT", TECHNOLOGY),
+    (3661, "TELEPHONE & TELEGRAPH APPARATUS", TECHNOLOGY),
+    (3663, "RADIO & TV BROADCASTING & COMMUNICATIONS EQUIPMENT", TECHNOLOGY),
+    (3669, "COMMUNICATIONS EQUIPMENT, NEC", TECHNOLOGY),
+    (3670, "ELECTRONIC COMPONENTS & ACCESSORIES", TECHNOLOGY),
+    (3672, "PRINTED CIRCUIT BOARDS", TECHNOLOGY),
+    (3674, "SEMICONDUCTORS & RELATED DEVICES", TECHNOLOGY),
+    (3677, "ELECTRONIC COILS, TRANSFORMERS & OTHER INDUCTORS", TECHNOLOGY),
+    (3678, "ELECTRONIC CONNECTORS", TECHNOLOGY),
+    (3679, "ELECTRONIC COMPONENTS, NEC", TECHNOLOGY),
+    (3690, "MISCELLANEOUS ELECTRICAL MACHINERY, EQUIPMENT & SUPPLIES", MANUFACTURING),
+    (3695, "MAGNETIC & OPTICAL RECORDING MEDIA", MANUFACTURING),
+    (3711, "MOTOR VEHICLES & PASSENGER CAR BODIES", MANUFACTURING),
+    (3713, "TRUCK & BUS BODIES", MANUFACTURING),
+    (3714, "MOTOR VEHICLE PARTS & ACCESSORIES", MANUFACTURING),
+    (3715, "TRUCK TRAILERS", MANUFACTURING),
+    (3716, "MOTOR HOMES", MANUFACTURING),
+    (3720, "AIRCRAFT & PARTS", MANUFACTURING),
+    (3721, "AIRCRAFT", MANUFACTURING),
+    (3724, "AIRCRAFT ENGINES & ENGINE PARTS", MANUFACTURING),
+    (3728, "AIRCRAFT PARTS & AUXILIARY EQUIPMENT, NEC", MANUFACTURING),
+    (3730, "SHIP & BOAT BUILDING & REPAIRING", MANUFACTURING),
+    (3743, "RAILROAD EQUIPMENT", MANUFACTURING),
+    (3751, "MOTORCYCLES, BICYCLES & PARTS", MANUFACTURING),
+    (3760, "GUIDED MISSILES & SPACE VEHICLES & PARTS", MANUFACTURING),
+    (3790, "MISCELLANEOUS TRANSPORTATION EQUIPMENT", MANUFACTURING),
+    (3812, "SEARCH, DETECTION, NAVAGATION, GUIDANCE, AERONAUTICAL SYS", MANUFACTURING),
+    (3821, "LABORATORY APPARATUS & FURNITURE", MANUFACTURING),
+    (3822, "AUTO CONTROLS FOR REGULATING RESIDENTIAL & COMML ENVIRONMENTS", MANUFACTURING),
+    (3823, "INDUSTRIAL INSTRUMENTS FOR MEASUREMENT, DISPLAY, AND CONTROL", MANUFACTURING),
+    (3824, "TOTALIZING FLUID METERS & COUNTING DEVICES", MANUFACTURING),
+    (3825, "INSTRUMENTS FOR MEAS & TESTING OF ELECTRICITY & ELEC SIGNALS", MANUFACTURING),
+    (3826, "LABORATORY ANALYTICAL INSTRUMENTS", MANUFACTURING),
+    (3827, "OPTICAL INSTRUMENTS & LENSES", MANUFACTURING),
+    (3829, "MEASURING & CONTROLLING DEVICES, NEC", MANUFACTURING),
+    (3841, "SURGICAL & MEDICAL INSTRUMENTS & APPARATUS", LIFE),
+    (3842, "ORTHOPEDIC, PROSTHETIC & SURGICAL APPLIANCES & SUPPLIES", LIFE),
+    (3843, "DENTAL EQUIPMENT & SUPPLIES", LIFE),
+    (3844, "X-RAY APPARATUS & TUBES & RELATED IRRADIATION APPARATUS", LIFE),
+    (3845, "ELECTROMEDICAL & ELECTROTHERAPEUTIC APPARATUS", LIFE),
+    (3851, "OPHTHALMIC GOODS", LIFE),
+    (3861, "PHOTOGRAPHIC EQUIPMENT & SUPPLIES", MANUFACTURING),
+    (3873, "WATCHES, CLOCKS, CLOCKWORK OPERATED DEVICES/PARTS", MANUFACTURING),
+    (3910, "JEWELRY, SILVERWARE & PLATED WARE", MANUFACTURING),
+    (3911, "JEWELRY, PRECIOUS METAL", MANUFACTURING),
+    (3942, "DOLLS & STUFFED TOYS", MANUFACTURING),
+    (3944, "GAMES, TOYS & CHILDREN'S VEHICLES (NO DOLLS & BICYCLES)", MANUFACTURING),
+    (3949, "SPORTING & ATHLETIC GOODS, NEC", MANUFACTURING),
+    (3950, "PENS, PENCILS & OTHER ARTISTS' MATERIALS", MANUFACTURING),
+    (3960, "COSTUME JEWELRY & NOVELTIES", MANUFACTURING),
+    (3990, "MISCELLANEOUS MANUFACTURING INDUSTRIES", MANUFACTURING),
+    (4011, "RAILROADS, LINE-HAUL OPERATING", ENERGY),
+    (4013, "RAILROAD SWITCHING & TERMINAL ESTABLISHMENTS", ENERGY),
+    (4100, "LOCAL & SUBURBAN TRANSIT & INTERURBAN HWY PASSENGER TRANS", ENERGY),
+    (4210, "TRUCKING & COURIER SERVICES (NO AIR)", ENERGY),
+    (4213, "TRUCKING (NO LOCAL)", ENERGY),
+    (4220, "PUBLIC WAREHOUSING & STORAGE", REAL_ESTATE),
+    (4231, "TERMINAL MAINTENANCE FACILITIES FOR MOTOR FREIGHT TRANSPORT", REAL_ESTATE),
+    (4400, "WATER TRANSPORTATION", ENERGY),
+    (4412, "DEEP SEA FOREIGN TRANSPORTATION OF FREIGHT", ENERGY),
+    (4512, "AIR TRANSPORTATION, SCHEDULED", ENERGY),
+    (4513, "AIR COURIER SERVICES", ENERGY),
+    (4522, "AIR TRANSPORTATION, NONSCHEDULED", ENERGY),
+    (4581, "AIRPORTS, FLYING FIELDS & AIRPORT TERMINAL SERVICES", ENERGY),
+    (4610, "PIPE LINES (NO NATURAL GAS)", ENERGY),
+    (4700, "TRANSPORTATION SERVICES", ENERGY),
+    (4731, "ARRANGEMENT OF TRANSPORTATION OF FREIGHT & CARGO", ENERGY),
+    (4812, "RADIOTELEPHONE COMMUNICATIONS", TECHNOLOGY),
+    (4813, "TELEPHONE COMMUNICATIONS (NO RADIOTELEPHONE)", TECHNOLOGY),
+    (4822, "TELEGRAPH & OTHER MESSAGE COMMUNICATIONS", TECHNOLOGY),
+    (4832, "RADIO BROADCASTING STATIONS", TRADE),
+    (4833, "TELEVISION BROADCASTING STATIONS", TRADE),
+    (4841, "CABLE & OTHER PAY TELEVISION SERVICES", TRADE),
+    (4899, "COMMUNICATIONS SERVICES, NEC", TECHNOLOGY),
+    (4900, "ELECTRIC, GAS & SANITARY SERVICES", ENERGY),
+    (4911, "ELECTRIC SERVICES", ENERGY),
+    (4922, "NATURAL GAS TRANSMISSION", ENERGY),
+    (4923, "NATURAL GAS TRANSMISISON & DISTRIBUTION", ENERGY),
+    (4924, "NATURAL GAS DISTRIBUTION", ENERGY),
+    (4931, "ELECTRIC & OTHER SERVICES COMBINED", ENERGY),
+    (4932, "GAS & OTHER SERVICES COMBINED", ENERGY),
+    (4941, "WATER SUPPLY", ENERGY),
+    (4950, "SANITARY SERVICES", IAS),
+    (4953, "REFUSE SYSTEMS", IAS),
+    (4955, "HAZARDOUS WASTE MANAGEMENT", IAS),
+    (4961, "STEAM & AIR-CONDITIONING SUPPLY", IAS),
+    (4991, "COGENERATION SERVICES & SMALL POWER PRODUCERS", IAS),
+    (5000, "WHOLESALE-DURABLE GOODS", TRADE),
+    (5010, "WHOLESALE-MOTOR VEHICLES & MOTOR VEHICLE PARTS & SUPPLIES", TRADE),
+    (5013, "WHOLESALE-MOTOR VEHICLE SUPPLIES & NEW PARTS", TRADE),
+    (5020, "WHOLESALE-FURNITURE & HOME FURNISHINGS", TRADE),
+    (5030, "WHOLESALE-LUMBER & OTHER CONSTRUCTION MATERIALS", TRADE),
+    (5031, "WHOLESALE-LUMBER, PLYWOOD, MILLWORK & WOOD PANELS", TRADE),
+    (5040, "WHOLESALE-PROFESSIONAL & COMMERCIAL EQUIPMENT & SUPPLIES", TRADE),
+    (5045, "WHOLESALE-COMPUTERS & PERIPHERAL EQUIPMENT & SOFTWARE", TRADE),
+    (5047, "WHOLESALE-MEDICAL, DENTAL & HOSPITAL EQUIPMENT & SUPPLIES", TRADE),
+    (5050, "WHOLESALE-METALS SERVICE CENTERS & OFFICES", TRADE),
+    (5051, "WHOLESALE-METALS SERVICE CENTERS & OFFICES", TRADE),
+    (5063, "WHOLESALE-ELECTRICAL APPARATUS & EQUIPMENT, WIRING SUPPLIES", TRADE),
+    (5064, "WHOLESALE-ELECTRICAL APPLIANCES, TV & RADIO SETS", TRADE),
+    (5065, "WHOLESALE-ELECTRONIC PARTS & EQUIPMENT, NEC", TRADE),
+    (5070, "WHOLESALE-HARDWARE & PLUMBING & HEATING EQUIPMENT & SUPPLIES", TRADE),
+    (5072, "WHOLESALE-HARDWARE", TRADE),
+    (5080, "WHOLESALE-MACHINERY, EQUIPMENT & SUPPLIES", TRADE),
+    (5082, "WHOLESALE-CONSTRUCTION & MINING (NO PETRO) MACHINERY & EQUIP", TRADE),
+    (5084, "WHOLESALE-INDUSTRIAL MACHINERY & EQUIPMENT", TRADE),
+    (5090, "WHOLESALE-MISC DURABLE GOODS", TRADE),
+    (5094, "WHOLESALE-JEWELRY, WATCHES, PRECIOUS STONES & METALS", TRADE),
+    (5099, "WHOLESALE-DURABLE GOODS, NEC", TRADE),
+    (5110, "WHOLESALE-PAPER AND PAPER PRODUCTS", TRADE),
+    (5122, "WHOLESALE-DRUGS PROPRIETARIES & DRUGGISTS' SUNDRIES", TRADE),
+    (5130, "WHOLESALE-APPAREL, PIECE GOODS & NOTIONS", TRADE),
+    (5140, "WHOLESALE-GROCERIES & RELATED PRODUCTS", TRADE),
+    (5141, "WHOLESALE-GROCERIES, GENERAL LINE", TRADE),
+    (5150, "WHOLESALE-FARM PRODUCT RAW MATERIALS", TRADE),
+    (5160, "WHOLESALE-CHEMICALS & ALLIED PRODUCTS", TRADE),
+    (5171, "WHOLESALE-PETROLEUM BULK STATIONS & TERMINALS", TRADE),
+    (5172, "WHOLESALE-PETROLEUM & PETROLEUM PRODUCTS (NO BULK STATIONS)", TRADE),
+    (5180, "WHOLESALE-BEER, WINE & DISTILLED ALCOHOLIC BEVERAGES", TRADE),
+    (5190, "WHOLESALE-MISCELLANEOUS NONDURABLE GOODS", TRADE),
+    (5200, "RETAIL-BUILDING MATERIALS, HARDWARE, GARDEN SUPPLY", TRADE),
+    (5211, "RETAIL-LUMBER & OTHER BUILDING MATERIALS DEALERS", TRADE),
+    (5271, "RETAIL-MOBILE HOME DEALERS", TRADE),
+    (5311, "RETAIL-DEPARTMENT STORES", TRADE),
+    (5331, "RETAIL-VARIETY STORES", TRADE),
+    (5399, "RETAIL-MISC GENERAL MERCHANDISE STORES", TRADE),
+    (5400, "RETAIL-FOOD STORES", TRADE),
+    (5411, "RETAIL-GROCERY STORES", TRADE),
+    (5412, "RETAIL-CONVENIENCE STORES", TRADE),
+    (5500, "RETAIL-AUTO DEALERS & GASOLINE STATIONS", TRADE),
+    (5531, "RETAIL-AUTO & HOME SUPPLY STORES", TRADE),
+    (5600, "RETAIL-APPAREL & ACCESSORY STORES", TRADE),
+    (5621, "RETAIL-WOMEN'S CLOTHING STORES", TRADE),
+    (5651, "RETAIL-FAMILY CLOTHING STORES", TRADE),
+    (5661, "RETAIL-SHOE STORES", TRADE),
+    (5700, "RETAIL-HOME FURNITURE, FURNISHINGS & EQUIPMENT STORES", TRADE),
+    (5712, "RETAIL-FURNITURE STORES", TRADE),
+    (5731, "RETAIL-RADIO, TV & CONSUMER ELECTRONICS STORES", TRADE),
+    (5734, "RETAIL-COMPUTER & COMPUTER SOFTWARE STORES", TRADE),
+    (5735, "RETAIL-RECORD & PRERECORDED TAPE STORES", TRADE),
+    (5810, "RETAIL-EATING & DRINKING PLACES", TRADE),
+    (5812, "RETAIL-EATING PLACES", TRADE),
+    (5900, "RETAIL-MISCELLANEOUS RETAIL", TRADE),
+    (5912, "RETAIL-DRUG STORES AND PROPRIETARY STORES", TRADE),
+    (5940, "RETAIL-MISCELLANEOUS SHOPPING GOODS STORES", TRADE),
+    (5944, "RETAIL-JEWELRY STORES", TRADE),
+    (5945, "RETAIL-HOBBY, TOY & GAME SHOPS", TRADE),
+    (5960, "RETAIL-NONSTORE RETAILERS", TRADE),
+    (5961, "RETAIL-CATALOG & MAIL-ORDER HOUSES", TRADE),
+    (5990, "RETAIL-RETAIL STORES, NEC", TRADE),
+    (6021, "NATIONAL COMMERCIAL BANKS", FINANCE),
+    (6022, "STATE COMMERCIAL BANKS", FINANCE),
+    (6029, "COMMERCIAL BANKS, NEC", FINANCE),
+    (6035, "SAVINGS INSTITUTION, FEDERALLY CHARTERED", FINANCE),
+    (6036, "SAVINGS INSTITUTIONS, NOT FEDERALLY CHARTERED", FINANCE),
+    (6099, "FUNCTIONS RELATED TO DEPOSITORY BANKING, NEC", FINANCE),
+    (6111, "FEDERAL & FEDERALLY-SPONSORED CREDIT AGENCIES", FINANCE),
+    (6141, "PERSONAL CREDIT INSTITUTIONS", FINANCE),
+    (6153, "SHORT-TERM BUSINESS CREDIT INSTITUTIONS", FINANCE),
+    (6159, "MISCELLANEOUS BUSINESS CREDIT INSTITUTION", FINANCE),
+    (6162, "MORTGAGE BANKERS & LOAN CORRESPONDENTS", FINANCE),
+    (6163, "LOAN BROKERS", FINANCE),
+    (6172, "FINANCE LESSORS", FINANCE),
+    (6189, "ASSET-BACKED SECURITIES", STRUCTURED),
+    (6199, "FINANCE SERVICES", FINANCE),
+    (6200, "SECURITY & COMMODITY BROKERS, DEALERS, EXCHANGES & SERVICES", FINANCE),
+    (6211, "SECURITY BROKERS, DEALERS & FLOTATION COMPANIES", FINANCE),
+    (6221, "COMMODITY CONTRACTS BROKERS & DEALERS", FINANCE),
+    (6282, "INVESTMENT ADVICE", FINANCE),
+    (6311, "LIFE INSURANCE", FINANCE),
+    (6321, "ACCIDENT & HEALTH INSURANCE", FINANCE),
+    (6324, "HOSPITAL & MEDICAL SERVICE PLANS", FINANCE),
+    (6331, "FIRE, MARINE & CASUALTY INSURANCE", FINANCE),
+    (6351, "SURETY INSURANCE", FINANCE),
+    (6361, "TITLE INSURANCE", FINANCE),
+    (6399, "INSURANCE CARRIERS, NEC", FINANCE),
+    (6411, "INSURANCE AGENTS, BROKERS & SERVICE", FINANCE),
+    (6500, "REAL ESTATE", REAL_ESTATE),
+    (6510, "REAL ESTATE OPERATORS (NO DEVELOPERS) & LESSORS", REAL_ESTATE),
+    (6512, "OPERATORS OF NONRESIDENTIAL BUILDINGS", REAL_ESTATE),
+    (6513, "OPERATORS OF APARTMENT BUILDINGS", REAL_ESTATE),
+    (6519, "LESSORS OF REAL PROPERTY, NEC", REAL_ESTATE),
+    (6531, "REAL ESTATE AGENTS & MANAGERS (FOR OTHERS)", REAL_ESTATE),
+    (6532, "REAL ESTATE DEALERS (FOR THEIR OWN ACCOUNT)", REAL_ESTATE),
+    (6552, "LAND SUBDIVIDERS & DEVELOPERS (NO CEMETERIES)", REAL_ESTATE),
+    (6770, "BLANK CHECKS", REAL_ESTATE),
+    (6792, "OIL ROYALTY TRADERS", ENERGY),
+    (6794, "PATENT OWNERS & LESSORS", TRADE),
+    (6795, "MINERAL ROYALTY TRADERS", ENERGY),
+    (6798, "REAL ESTATE INVESTMENT TRUSTS", REAL_ESTATE),
+    (6799, "INVESTORS, NEC", FINANCE),
+    (7000, "HOTELS, ROOMING HOUSES, CAMPS & OTHER LODGING PLACES", REAL_ESTATE),
+    (7011, "HOTELS & MOTELS", REAL_ESTATE),
+    (7200, "SERVICES-PERSONAL SERVICES", TRADE),
+    (7310, "SERVICES-ADVERTISING", TRADE),
+    (7311, "SERVICES-ADVERTISING AGENCIES", TRADE),
+    (7320, "SERVICES-CONSUMER CREDIT REPORTING, COLLECTION AGENCIES", TRADE),
+    (7330, "SERVICES-MAILING, REPRODUCTION, COMMERCIAL ART & PHOTOGRAPHY", TRADE),
+    (7331, "SERVICES-DIRECT MAIL ADVERTISING SERVICES", TRADE),
+    (7340, "SERVICES-TO DWELLINGS & OTHER BUILDINGS", TRADE),
+    (7350, "SERVICES-MISCELLANEOUS EQUIPMENT RENTAL & LEASING", TRADE),
+    (7359, "SERVICES-EQUIPMENT RENTAL & LEASING, NEC", TRADE),
+    (7361, "SERVICES-EMPLOYMENT AGENCIES", TRADE),
+    (7363, "SERVICES-HELP SUPPLY SERVICES", TRADE),
+    (7370, "SERVICES-COMPUTER PROGRAMMING, DATA PROCESSING, ETC.", TECHNOLOGY),
+    (7371, "SERVICES-COMPUTER PROGRAMMING SERVICES", TECHNOLOGY),
+    (7372, "SERVICES-PREPACKAGED SOFTWARE", TECHNOLOGY),
+    (7373, "SERVICES-COMPUTER INTEGRATED SYSTEMS DESIGN", TECHNOLOGY),
+    (7374, "SERVICES-COMPUTER PROCESSING & DATA PREPARATION", TECHNOLOGY),
+    (7377, "SERVICES-COMPUTER RENTAL & LEASING", TECHNOLOGY),
+    (7380, "SERVICES-MISCELLANEOUS BUSINESS SERVICES", TRADE),
+    (7381, "SERVICES-DETECTIVE, GUARD & ARMORED CAR SERVICES", TRADE),
+    (7384, "SERVICES-PHOTOFINISHING LABORATORIES", TRADE),
+    (7385, "SERVICES-TELEPHONE INTERCONNECT SYSTEMS", TRADE),
+    (7389, "SERVICES-BUSINESS SERVICES, NEC", TRADE),
+    (7500, "SERVICES-AUTOMOTIVE REPAIR, SERVICES & PARKING", TRADE),
+    (7510, "SERVICES-AUTO RENTAL & LEASING (NO DRIVERS)", TRADE),
+    (7600, "SERVICES-MISCELLANEOUS REPAIR SERVICES", TRADE),
+    (7812, "SERVICES-MOTION PICTURE & VIDEO TAPE PRODUCTION", TRADE),
+    (7819, "SERVICES-ALLIED TO MOTION PICTURE PRODUCTION", TRADE),
+    (7822, "SERVICES-MOTION PICTURE & VIDEO TAPE DISTRIBUTION", TRADE),
+    (7829, "SERVICES-ALLIED TO MOTION PICTURE DISTRIBUTION", TRADE),
+    (7830, "SERVICES-MOTION PICTURE THEATERS", TRADE),
+    (7841, "SERVICES-VIDEO TAPE RENTAL", TRADE),
+    (7900, "SERVICES-AMUSEMENT & RECREATION SERVICES", TRADE),
+    (7948, "SERVICES-RACING, INCLUDING TRACK OPERATION", TRADE),
+    (7990, "SERVICES-MISCELLANEOUS AMUSEMENT & RECREATION", TRADE),
+    (7997, "SERVICES-MEMBERSHIP SPORTS & RECREATION CLUBS", TRADE),
+    (8000, "SERVICES-HEALTH SERVICES", LIFE),
+    (8011, "SERVICES-OFFICES & CLINICS OF DOCTORS OF MEDICINE", LIFE),
+    (8050, "SERVICES-NURSING & PERSONAL CARE FACILITIES", LIFE),
+    (8051, "SERVICES-SKILLED NURSING CARE FACILITIES", LIFE),
+    (8060, "SERVICES-HOSPITALS", LIFE),
+    (8062, "SERVICES-GENERAL MEDICAL & SURGICAL HOSPITALS, NEC", LIFE),
+    (8071, "SERVICES-MEDICAL LABORATORIES", LIFE),
+    (8082, "SERVICES-HOME HEALTH CARE SERVICES", LIFE),
+    (8090, "SERVICES-MISC HEALTH & ALLIED SERVICES, NEC", LIFE),
+    (8093, "SERVICES-SPECIALTY OUTPATIENT FACILITIES, NEC", LIFE),
+    (8111, "SERVICES-LEGAL SERVICES", TRADE),
+    (8200, "SERVICES-EDUCATIONAL SERVICES", TRADE),
+    (8300, "SERVICES-SOCIAL SERVICES", TRADE),
+    (8351, "SERVICES-CHILD DAY CARE SERVICES", TRADE),
+    (8600, "SERVICES-MEMBERSHIP ORGANIZATIONS", TRADE),
+    (8700, "SERVICES-ENGINEERING, ACCOUNTING, RESEARCH, MANAGEMENT", TRADE),
+    (8711, "SERVICES-ENGINEERING SERVICES", TRADE),
+    (8731, "SERVICES-COMMERCIAL PHYSICAL & BIOLOGICAL RESEARCH", LIFE),
+    (8734, "SERVICES-TESTING LABORATORIES", TRADE),
+    (8741, "SERVICES-MANAGEMENT SERVICES", TRADE),
+    (8742, "SERVICES-MANAGEMENT CONSULTING SERVICES", TRADE),
+    (8744, "SERVICES-FACILITIES SUPPORT MANAGEMENT SERVICES", TRADE),
+    (8880, "AMERICAN DEPOSITARY RECEIPTS", INTERNATIONAL),
+    (8888, "FOREIGN GOVERNMENTS", INTERNATIONAL),
+    (8900, "SERVICES-SERVICES, NEC", TRADE),
+    (9721, "INTERNATIONAL AFFAIRS", INTERNATIONAL),
+    (9995, "NON-OPERATING ESTABLISHMENTS", REAL_ESTATE),
+];
+
+/// Look up a SIC code
+pub fn sic_code(code: u16) -> Option<SicCode> {
+    SIC_CODES
+        .binary_search_by_key(&code, |&(c, _, _)| c)
+        .ok()
+        .map(|i| {
+            let (code, description, office) = SIC_CODES[i];
+            SicCode { code, description, office }
+        })
+}
+
+static STATE_CODES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+fn get_state_codes() -> &'static HashMap<&'static str, &'static str> {
+    STATE_CODES.get_or_init(|| {
+        HashMap::from([
+        ("AL", "ALABAMA"),
+        ("AK", "ALASKA"),
+        ("AZ", "ARIZONA"),
+        ("AR", "ARKANSAS"),
+        ("CA", "CALIFORNIA"),
+        ("CO", "COLORADO"),
+        ("CT", "CONNECTICUT"),
+        ("DE", "DELAWARE"),
+        ("DC", "DISTRICT OF COLUMBIA"),
+        ("FL", "FLORIDA"),
+        ("GA", "GEORGIA"),
+        ("HI", "HAWAII"),
+        ("ID", "IDAHO"),
+        ("IL", "ILLINOIS"),
+        ("IN", "INDIANA"),
+        ("IA", "IOWA"),
+        ("KS", "KANSAS"),
+        ("KY", "KENTUCKY"),
+        ("LA", "LOUISIANA"),
+        ("ME", "MAINE"),
+        ("MD", "MARYLAND"),
+        ("MA", "MASSACHUSETTS"),
+        ("MI", "MICHIGAN"),
+        ("MN", "MINNESOTA"),
+        ("MS", "MISSISSIPPI"),
+        ("MO", "MISSOURI"),
+        ("MT", "MONTANA"),
+        ("NE", "NEBRASKA"),
+        ("NV", "NEVADA"),
+        ("NH", "NEW HAMPSHIRE"),
+        ("NJ", "NEW JERSEY"),
+        ("NM", "NEW MEXICO"),
+        ("NY", "NEW YORK"),
+        ("NC", "NORTH CAROLINA"),
+        ("ND", "NORTH DAKOTA"),
+        ("OH", "OHIO"),
+        ("OK", "OKLAHOMA"),
+        ("OR", "OREGON"),
+        ("PA", "PENNSYLVANIA"),
+        ("RI", "RHODE ISLAND"),
+        ("SC", "SOUTH CAROLINA"),
+        ("SD", "SOUTH DAKOTA"),
+        ("TN", "TENNESSEE"),
+        ("TX", "TEXAS"),
+        ("X1", "UNITED STATES"),
+        ("UT", "UTAH"),
+        ("VT", "VERMONT"),
+        ("VA", "VIRGINIA"),
+        ("WA", "WASHINGTON"),
+        ("WV", "WEST VIRGINIA"),
+        ("WI", "WISCONSIN"),
+        ("WY", "WYOMING"),
+        ("A0", "ALBERTA, CANADA"),
+        ("A1", "BRITISH COLUMBIA, CANADA"),
+        ("A2", "MANITOBA, CANADA"),
+        ("A3", "NEW BRUNSWICK, CANADA"),
+        ("A4", "NEWFOUNDLAND, CANADA"),
+        ("A5", "NOVA SCOTIA, CANADA"),
+        ("A6", "ONTARIO, CANADA"),
+        ("A7", "PRINCE EDWARD ISLAND, CANADA"),
+        ("A8", "QUEBEC, CANADA"),
+        ("A9", "SASKATCHEWAN, CANADA"),
+        ("B0", "YUKON, CANADA"),
+        ("Z4", "CANADA (FEDERAL LEVEL)"),
+        ("B2", "AFGHANISTAN"),
+        ("Y6", "ALAND ISLANDS"),
+        ("B3", "ALBANIA"),
+        ("B4", "ALGERIA"),
+        ("B5", "AMERICAN SAMOA"),
+        ("B6", "ANDORRA"),
+        ("B7", "ANGOLA"),
+        ("1A", "ANGUILLA"),
+        ("B8", "ANTARCTICA"),
+        ("B9", "ANTIGUA AND BARBUDA"),
+        ("C1", "ARGENTINA"),
+        ("1B", "ARMENIA"),
+        ("1C", "ARUBA"),
+        ("C3", "AUSTRALIA"),
+        ("C4", "AUSTRIA"),
+        ("1D", "AZERBAIJAN"),
+        ("C5", "BAHAMAS"),
+        ("C6", "BAHRAIN"),
+        ("C7", "BANGLADESH"),
+        ("C8", "BARBADOS"),
+        ("1F", "BELARUS"),
+        ("C9", "BELGIUM"),
+        ("D1", "BELIZE"),
+        ("G6", "BENIN"),
+        ("D0", "BERMUDA"),
+        ("D2", "BHUTAN"),
+        ("D3", "BOLIVIA"),
+        ("1E", "BOSNIA AND HERZEGOVINA"),
+        ("B1", "BOTSWANA"),
+        ("D4", "BOUVET ISLAND"),
+        ("D5", "BRAZIL"),
+        ("D6", "BRITISH INDIAN OCEAN TERRITORY"),
+        ("D9", "BRUNEI DARUSSALAM"),
+        ("E0", "BULGARIA"),
+        ("X2", "BURKINA FASO"),
+        ("E2", "BURUNDI"),
+        ("E3", "CAMBODIA"),
+        ("E4", "CAMEROON"),
+        ("E8", "CAPE VERDE"),
+        ("E9", "CAYMAN ISLANDS"),
+        ("F0", "CENTRAL AFRICAN REPUBLIC"),
+        ("F2", "CHAD"),
+        ("F3", "CHILE"),
+        ("F4", "CHINA"),
+        ("F6", "CHRISTMAS ISLAND"),
+        ("F7", "COCOS (KEELING) ISLANDS"),
+        ("F8", "COLOMBIA"),
+        ("G0", "CONGO"),
+        ("Y3", "CONGO, THE DEMOCRATIC REPUBLIC OF THE"),
+        ("G1", "COOK ISLANDS"),
+        ("G2", "COSTA RICA"),
+        ("L7", "COTE D'IVOIRE"),
+        ("1M", "CROATIA"),
+        ("G3", "CUBA"),
+        ("G4", "CYPRUS"),
+        ("2N", "CZECH REPUBLIC"),
+        ("G7", "DENMARK"),
+        ("1G", "DJIBOUTI"),
+        ("G9", "DOMINICA"),
+        ("G8", "DOMINICAN REPUBLIC"),
+        ("H1", "ECUADOR"),
+        ("H2", "EGYPT"),
+        ("H3", "EL SALVADOR"),
+        ("H4", "EQUATORIAL GUINEA"),
+        ("1J", "ERITREA"),
+        ("1H", "ESTONIA"),
+        ("H5", "ETHIOPIA"),
+        ("H7", "FALKLAND ISLANDS (MALVINAS)"),
+        ("H6", "FAROE ISLANDS"),
+        ("H8", "FIJI"),
+        ("H9", "FINLAND"),
+        ("I0", "FRANCE"),
+        ("I3", "FRENCH GUIANA"),
+        ("I4", "FRENCH POLYNESIA"),
+        ("2C", "FRENCH SOUTHERN TERRITORIES"),
+        ("I5", "GABON"),
+        ("J1", "GAMBIA"),
+        ("2Q", "GEORGIA"),
+        ("2M", "GERMANY"),
+        ("J2", "GHANA"),
+        ("J3", "GIBRALTAR"),
+        ("J4", "GREECE"),
+        ("J5", "GREENLAND"),
+        ("J6", "GRENADA"),
+        ("J7", "GUADELOUPE"),
+        ("GU", "GUAM"),
+        ("J8", "GUATEMALA"),
+        ("Y7", "GUERNSEY"),
+        ("J9", "GUINEA"),
+        ("S0", "GUINEA-BISSAU"),
+        ("K0", "GUYANA"),
+        ("K1", "HAITI"),
+        ("K4", "HEARD ISLAND AND MCDONALD ISLANDS"),
+        ("X4", "HOLY SEE (VATICAN CITY STATE)"),
+        ("K2", "HONDURAS"),
+        ("K3", "HONG KONG"),
+        ("K5", "HUNGARY"),
+        ("K6", "ICELAND"),
+        ("K7", "INDIA"),
+        ("K8", "INDONESIA"),
+        ("K9", "IRAN, ISLAMIC REPUBLIC OF"),
+        ("L0", "IRAQ"),
+        ("L2", "IRELAND"),
+        ("Y8", "ISLE OF MAN"),
+        ("L3", "ISRAEL"),
+        ("L6", "ITALY"),
+        ("L8", "JAMAICA"),
+        ("M0", "JAPAN"),
+        ("Y9", "JERSEY"),
+        ("M2", "JORDAN"),
+        ("1P", "KAZAKHSTAN"),
+        ("M3", "KENYA"),
+        ("J0", "KIRIBATI"),
+        ("M4", "KOREA, DEMOCRATIC PEOPLE'S REPUBLIC OF"),
+        ("M5", "KOREA, REPUBLIC OF"),
+        ("M6", "KUWAIT"),
+        ("1N", "KYRGYZSTAN"),
+        ("M7", "LAO PEOPLE'S DEMOCRATIC REPUBLIC"),
+        ("1R", "LATVIA"),
+        ("M8", "LEBANON"),
+        ("M9", "LESOTHO"),
+        ("N0", "LIBERIA"),
+        ("N1", "LIBYAN ARAB JAMAHIRIYA"),
+        ("N2", "LIECHTENSTEIN"),
+        ("1Q", "LITHUANIA"),
+        ("N4", "LUXEMBOURG"),
+        ("N5", "MACAU"),
+        ("1U", "MACEDONIA, THE FORMER YUGOSLAV REPUBLIC OF"),
+        ("N6", "MADAGASCAR"),
+        ("N7", "MALAWI"),
+        ("N8", "MALAYSIA"),
+        ("N9", "MALDIVES"),
+        ("O0", "MALI"),
+        ("O1", "MALTA"),
+        ("1T", "MARSHALL ISLANDS"),
+        ("O2", "MARTINIQUE"),
+        ("O3", "MAURITANIA"),
+        ("O4", "MAURITIUS"),
+        ("2P", "MAYOTTE"),
+        ("O5", "MEXICO"),
+        ("1K", "MICRONESIA, FEDERATED STATES OF"),
+        ("1S", "MOLDOVA, REPUBLIC OF"),
+        ("O9", "MONACO"),
+        ("P0", "MONGOLIA"),
+        ("Z5", "MONTENEGRO"),
+        ("P1", "MONTSERRAT"),
+        ("P2", "MOROCCO"),
+        ("P3", "MOZAMBIQUE"),
+        ("E1", "MYANMAR"),
+        ("T6", "NAMIBIA"),
+        ("P5", "NAURU"),
+        ("P6", "NEPAL"),
+        ("P7", "NETHERLANDS"),
+        ("P8", "NETHERLANDS ANTILLES"),
+        ("1W", "NEW CALEDONIA"),
+        ("Q2", "NEW ZEALAND"),
+        ("Q3", "NICARAGUA"),
+        ("Q4", "NIGER"),
+        ("Q5", "NIGERIA"),
+        ("Q6", "NIUE"),
+        ("Q7", "NORFOLK ISLAND"),
+        ("1V", "NORTHERN MARIANA ISLANDS"),
+        ("Q8", "NORWAY"),
+        ("P4", "OMAN"),
+        ("R0", "PAKISTAN"),
+        ("1Y", "PALAU"),
+        ("1X", "PALESTINIAN TERRITORY, OCCUPIED"),
+        ("R1", "PANAMA"),
+        ("R2", "PAPUA NEW GUINEA"),
+        ("R4", "PARAGUAY"),
+        ("R5", "PERU"),
+        ("R6", "PHILIPPINES"),
+        ("R8", "PITCAIRN"),
+        ("R9", "POLAND"),
+        ("S1", "PORTUGAL"),
+        ("PR", "PUERTO RICO"),
+        ("S3", "QATAR"),
+        ("S4", "REUNION"),
+        ("S5", "ROMANIA"),
+        ("1Z", "RUSSIAN FEDERATION"),
+        ("S6", "RWANDA"),
+        ("Z0", "SAINT BARTHELEMY"),
+        ("U8", "SAINT HELENA"),
+        ("U7", "SAINT KITTS AND NEVIS"),
+        ("U9", "SAINT LUCIA"),
+        ("Z1", "SAINT MARTIN"),
+        ("V0", "SAINT PIERRE AND MIQUELON"),
+        ("V1", "SAINT VINCENT AND THE GRENADINES"),
+        ("Y0", "SAMOA"),
+        ("S8", "SAN MARINO"),
+        ("S9", "SAO TOME AND PRINCIPE"),
+        ("T0", "SAUDI ARABIA"),
+        ("T1", "SENEGAL"),
+        ("Z2", "SERBIA"),
+        ("T2", "SEYCHELLES"),
+        ("T8", "SIERRA LEONE"),
+        ("U0", "SINGAPORE"),
+        ("2B", "SLOVAKIA"),
+        ("2A", "SLOVENIA"),
+        ("D7", "SOLOMON ISLANDS"),
+        ("U1", "SOMALIA"),
+        ("T3", "SOUTH AFRICA"),
+        ("1L", "SOUTH GEORGIA AND THE SOUTH SANDWICH ISLANDS"),
+        ("U3", "SPAIN"),
+        ("F1", "SRI LANKA"),
+        ("V2", "SUDAN"),
+        ("V3", "SURINAME"),
+        ("L9", "SVALBARD AND JAN MAYEN"),
+        ("V6", "SWAZILAND"),
+        ("V7", "SWEDEN"),
+        ("V8", "SWITZERLAND"),
+        ("V9", "SYRIAN ARAB REPUBLIC"),
+        ("F5", "TAIWAN, PROVINCE OF CHINA"),
+        ("2D", "TAJIKISTAN"),
+        ("W0", "TANZANIA, UNITED REPUBLIC OF"),
+        ("W1", "THAILAND"),
+        ("Z3", "TIMOR-LESTE"),
+        ("W2", "TOGO"),
+        ("W3", "TOKELAU"),
+        ("W4", "TONGA"),
+        ("W5", "TRINIDAD AND TOBAGO"),
+        ("W6", "TUNISIA"),
+        ("W8", "TURKEY"),
+        ("2E", "TURKMENISTAN"),
+        ("W7", "TURKS AND CAICOS ISLANDS"),
+        ("2G", "TUVALU"),
+        ("W9", "UGANDA"),
+        ("2H", "UKRAINE"),
+        ("C0", "UNITED ARAB EMIRATES"),
+        ("X0", "UNITED KINGDOM"),
+        ("2J", "UNITED STATES MINOR OUTLYING ISLANDS"),
+        ("X3", "URUGUAY"),
+        ("2K", "UZBEKISTAN"),
+        ("2L", "VANUATU"),
+        ("X5", "VENEZUELA"),
+        ("Q1", "VIET NAM"),
+        ("D8", "VIRGIN ISLANDS, BRITISH"),
+        ("VI", "VIRGIN ISLANDS, U.S."),
+        ("X8", "WALLIS AND FUTUNA"),
+        ("U5", "WESTERN SAHARA"),
+        ("T7", "YEMEN"),
+        ("Y4", "ZAMBIA"),
+        ("Y5", "ZIMBABWE"),
+        ("XX", "UNKNOWN"),
+        ])
+    })
+}
+
+/// Resolve an EDGAR state or country code (`DE`, `X0`, `A6`) to its name
+pub fn state_name(code: &str) -> Option<&'static str> {
+    get_state_codes().get(code.trim().to_ascii_uppercase().as_str()).copied()
+}
+
+/// Add descriptive fields next to SIC and state codes throughout the header.
+///
+/// `assigned-sic` gains `assigned-sic-description` and `sic-office`;
+/// `state` and `state-of-incorporation` gain a `-name` sibling. Unknown
+/// codes are left without additions.
+pub fn enrich_codes(fields: &mut HashMap<String, MetadataValue>) {
+    let mut additions = Vec::new();
+
+    for (key, value) in fields.iter_mut() {
+        match value {
+            MetadataValue::String(s) => match standardize_key(key).as_ref() {
+                "assigned-sic" => {
+                    if let Some(sic) = parse_sic(s).and_then(sic_code) {
+                        let office_key = if key == "assigned-sic" {
+                            "sic-office".to_string()
+                        } else {
+                            derived_key(key, "office")
+                        };
+                        additions.push((derived_key(key, "description"), sic.description));
+                        additions.push((office_key, sic.office));
+                    }
+                }
+                "state" | "state-of-incorporation" => {
+                    if let Some(name) = state_name(s) {
+                        additions.push((derived_key(key, "name"), name));
+                    }
+                }
+                _ => {}
+            },
+            MetadataValue::Object(obj) => enrich_codes(obj),
+            MetadataValue::List(items) => {
+                for item in items {
+                    if let MetadataValue::Object(obj) = item {
+                        enrich_codes(obj);
+                    }
+                }
+            }
+        }
+    }
+
+    for (key, value) in additions {
+        fields.entry(key).or_insert_with(|| MetadataValue::string(value));
+    }
+}
+
+/// Extract the SIC number from `7370` or `SERVICES-COMPUTER PROGRAMMING [7370]`
+fn parse_sic(value: &str) -> Option<u16> {
+    let digits = match (value.find('['), value.find(']')) {
+        (Some(start), Some(end)) if end > start => &value[start + 1..end],
+        _ => value,
+    };
+    digits.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let sic = sic_code(7372).unwrap();
+        assert_eq!(sic.description, "SERVICES-PREPACKAGED SOFTWARE");
+        assert_eq!(sic.office, TECHNOLOGY);
+        assert!(sic_code(1).is_none());
+        assert_eq!(state_name("de"), Some("DELAWARE"));
+        assert_eq!(state_name("X0"), Some("UNITED KINGDOM"));
+    }
+
+    #[test]
+    fn test_enrich_codes() {
+        let mut company = HashMap::new();
+        company.insert("assigned-sic".to_string(), MetadataValue::string("6189"));
+        company.insert("state-of-incorporation".to_string(), MetadataValue::string("DE"));
+        let mut fields = HashMap::new();
+        fields.insert("company-data".to_string(), MetadataValue::Object(company));
+
+        enrich_codes(&mut fields);
+
+        let company = fields["company-data"].as_object().unwrap();
+        assert_eq!(company["assigned-sic"].as_str(), Some("6189"));
+        assert_eq!(company["assigned-sic-description"].as_str(), Some("ASSET-BACKED SECURITIES"));
+        assert_eq!(company["sic-office"].as_str(), Some(STRUCTURED));
+        assert_eq!(company["state-of-incorporation-name"].as_str(), Some("DELAWARE"));
+    }
+}
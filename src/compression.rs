@@ -0,0 +1,148 @@
+//! Transparent decompression of stored filings
+//!
+//! Compression is detected from magic bytes. Each codec is behind its own
+//! feature (`gzip`, `zstd`, `bzip2`, or all of them via `compression`).
+
+use crate::error::{ParseError, Result};
+use std::borrow::Cow;
+use std::io::Read;
+
+/// Largest expansion trusted when sizing the output buffer. SGML filings
+/// rarely compress better than 20:1; a larger gzip size trailer is forged
+/// or corrupt.
+const MAX_RATIO: usize = 32;
+
+/// Compression codec detected from magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Compression {
+    /// Detect the codec from the first bytes of the data
+    pub fn detect(data: &[u8]) -> Self {
+        if data.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if data.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if data.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else {
+            Compression::None
+        }
+    }
+
+    /// Name of the feature that enables this codec
+    fn feature(&self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+        }
+    }
+}
+
+/// Decompress data if it is compressed, borrowing it unchanged otherwise.
+///
+/// The whole output is held in one buffer, since the parser needs the
+/// submission as a single slice. Use [`decompressing_reader`] to process
+/// the decompressed bytes as a stream instead.
+pub fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+    let compression = Compression::detect(data);
+    if compression == Compression::None {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    // Decode straight from the input into one output buffer
+    let mut output = Vec::with_capacity(size_hint(data, compression));
+    decompressing_reader(data)?.read_to_end(&mut output)?;
+    Ok(Cow::Owned(output))
+}
+
+/// Wrap a reader in the decoder matching its first bytes.
+///
+/// The reader is buffered so the magic bytes can be inspected without
+/// consuming them.
+pub fn decompressing_reader<'a, R: Read + Send + 'a>(reader: R) -> Result<Box<dyn Read + Send + 'a>> {
+    use std::io::BufRead;
+
+    let mut reader = std::io::BufReader::new(reader);
+    let compression = Compression::detect(reader.fill_buf()?);
+
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?)),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => Ok(Box::new(bzip2::bufread::MultiBzDecoder::new(reader))),
+        #[allow(unreachable_patterns)]
+        other => Err(ParseError::UnsupportedCompression(other.feature())),
+    }
+}
+
+/// Estimate the decompressed size to avoid regrowing the output buffer
+fn size_hint(data: &[u8], compression: Compression) -> usize {
+    match compression {
+        // Gzip stores the uncompressed size (mod 2^32) in its last 4 bytes
+        Compression::Gzip if data.len() >= 4 => {
+            let tail: [u8; 4] = data[data.len() - 4..].try_into().unwrap_or_default();
+            (u32::from_le_bytes(tail) as usize).clamp(data.len(), data.len().saturating_mul(MAX_RATIO))
+        }
+        _ => data.len() * 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+        assert_eq!(Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd]), Compression::Zstd);
+        assert_eq!(Compression::detect(b"BZh91AY"), Compression::Bzip2);
+        assert_eq!(Compression::detect(b"<SEC-DOCUMENT>"), Compression::None);
+    }
+
+    #[test]
+    fn test_uncompressed_is_borrowed() {
+        let data = b"<SUBMISSION>";
+        assert!(matches!(decompress(data).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_size_hint_capped() {
+        // A 4 GiB size trailer on 20 bytes of gzip
+        let mut forged = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0x03, 0x03, 0, 0, 0, 0, 0];
+        forged.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(size_hint(&forged, Compression::Gzip), forged.len() * MAX_RATIO);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_round_trip() {
+        use std::io::Write;
+
+        let data = b"<SUBMISSION>\n<TYPE>8-K\n".repeat(100);
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&data).unwrap();
+        let gz = gz.finish().unwrap();
+
+        let zst = zstd::encode_all(&data[..], 1).unwrap();
+
+        let mut bz = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+        bz.write_all(&data).unwrap();
+        let bz = bz.finish().unwrap();
+
+        for compressed in [gz, zst, bz] {
+            assert_eq!(decompress(&compressed).unwrap().as_ref(), &data[..]);
+        }
+    }
+}
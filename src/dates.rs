@@ -0,0 +1,253 @@
+//! Date and datetime normalization for header values
+//!
+//! EDGAR headers write dates as `20010102`, acceptance timestamps as
+//! `20010102123456` and fiscal year ends as `1231`. These types parse those
+//! forms and display them as ISO-8601.
+
+use crate::error::ParseError;
+use crate::header_mappings::{derived_key, standardize_key};
+use crate::types::MetadataValue;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Calendar date (`YYYYMMDD` or `YYYY-MM-DD`), displayed as `YYYY-MM-DD`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SecDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for SecDate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: Vec<u8> = s.trim().bytes().filter(|&b| b != b'-').collect();
+        let digits = ascii_digits(&digits, 8).ok_or_else(|| invalid("date", s))?;
+        let date = SecDate {
+            year: number(&digits[0..4]) as u16,
+            month: number(&digits[4..6]) as u8,
+            day: number(&digits[6..8]) as u8,
+        };
+        if (1..=12).contains(&date.month) && (1..=31).contains(&date.day) {
+            Ok(date)
+        } else {
+            Err(invalid("date", s))
+        }
+    }
+}
+
+impl fmt::Display for SecDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Timestamp (`YYYYMMDDhhmmss`), displayed as `YYYY-MM-DDThh:mm:ss`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SecDateTime {
+    pub date: SecDate,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl FromStr for SecDateTime {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = ascii_digits(s.trim().as_bytes(), 14).ok_or_else(|| invalid("datetime", s))?;
+        let datetime = SecDateTime {
+            date: SecDate {
+                year: number(&digits[0..4]) as u16,
+                month: number(&digits[4..6]) as u8,
+                day: number(&digits[6..8]) as u8,
+            },
+            hour: number(&digits[8..10]) as u8,
+            minute: number(&digits[10..12]) as u8,
+            second: number(&digits[12..14]) as u8,
+        };
+        let date_valid = (1..=12).contains(&datetime.date.month) && (1..=31).contains(&datetime.date.day);
+        if date_valid && datetime.hour < 24 && datetime.minute < 60 && datetime.second < 61 {
+            Ok(datetime)
+        } else {
+            Err(invalid("datetime", s))
+        }
+    }
+}
+
+impl fmt::Display for SecDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Recurring month and day (`MMDD`), displayed as ISO-8601 `--MM-DD`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonthDay {
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for MonthDay {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = ascii_digits(s.trim().as_bytes(), 4).ok_or_else(|| invalid("month and day", s))?;
+        let month_day = MonthDay {
+            month: number(&digits[0..2]) as u8,
+            day: number(&digits[2..4]) as u8,
+        };
+        if (1..=12).contains(&month_day.month) && (1..=31).contains(&month_day.day) {
+            Ok(month_day)
+        } else {
+            Err(invalid("month and day", s))
+        }
+    }
+}
+
+impl fmt::Display for MonthDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "--{:02}-{:02}", self.month, self.day)
+    }
+}
+
+/// `bytes` if it is exactly `len` ASCII digits, which makes every slice
+/// of it safe to take
+fn ascii_digits(bytes: &[u8], len: usize) -> Option<&[u8]> {
+    (bytes.len() == len && bytes.iter().all(u8::is_ascii_digit)).then_some(bytes)
+}
+
+/// Value of a run of ASCII digits
+fn number(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |n, &d| n * 10 + u32::from(d - b'0'))
+}
+
+fn invalid(kind: &str, value: &str) -> ParseError {
+    ParseError::InvalidDate(format!("{} {:?}", kind, value))
+}
+
+/// Standardized keys holding `YYYYMMDD` dates
+const DATE_KEYS: &[&str] = &[
+    "filing-date",
+    "period",
+    "date-of-filing-date-change",
+    "effectiveness-date",
+    "date-changed",
+    "action-date",
+    "recieved-date",
+];
+
+/// Keys whose value is a `filename : date` composite
+const COMPOSITE_KEYS: &[&str] = &["sec-document", "sec-header", "ims-document", "ims-header"];
+
+/// Rewrite header dates as ISO-8601 and split `filename : date` composites.
+///
+/// Works with standardized and original keys. Values that don't parse are
+/// left untouched.
+pub fn normalize_header_dates(fields: &mut HashMap<String, MetadataValue>) {
+    let composites: Vec<String> = fields
+        .keys()
+        .filter(|key| COMPOSITE_KEYS.contains(&standardize_key(key).as_ref()))
+        .cloned()
+        .collect();
+
+    for key in composites {
+        if let Some(MetadataValue::String(value)) = fields.get(&key) {
+            if let Some((filename, date)) = value.split_once(" : ") {
+                let filename = filename.trim().to_string();
+                let date = normalize_value(date.trim(), "filing-date");
+                fields.insert(derived_key(&key, "filename"), MetadataValue::String(filename));
+                fields.insert(derived_key(&key, "date"), MetadataValue::String(date));
+                fields.remove(&key);
+            }
+        }
+    }
+
+    for (key, value) in fields.iter_mut() {
+        normalize_entry(key, value);
+    }
+}
+
+fn normalize_entry(key: &str, value: &mut MetadataValue) {
+    match value {
+        MetadataValue::String(s) => {
+            *s = normalize_value(s, &standardize_key(key));
+        }
+        MetadataValue::List(items) => {
+            for item in items {
+                normalize_entry(key, item);
+            }
+        }
+        MetadataValue::Object(obj) => {
+            for (key, value) in obj.iter_mut() {
+                normalize_entry(key, value);
+            }
+        }
+    }
+}
+
+fn normalize_value(value: &str, standard_key: &str) -> String {
+    let normalized = if DATE_KEYS.contains(&standard_key) {
+        value.parse::<SecDate>().map(|d| d.to_string())
+    } else if standard_key == "acceptance-datetime" {
+        value.parse::<SecDateTime>().map(|d| d.to_string())
+    } else if standard_key == "fiscal-year-end" {
+        value.parse::<MonthDay>().map(|d| d.to_string())
+    } else {
+        return value.to_string();
+    };
+    normalized.unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dates() {
+        assert_eq!("20010102".parse::<SecDate>().unwrap().to_string(), "2001-01-02");
+        assert_eq!(
+            "20010102123456".parse::<SecDateTime>().unwrap().to_string(),
+            "2001-01-02T12:34:56"
+        );
+        assert_eq!("1231".parse::<MonthDay>().unwrap().to_string(), "--12-31");
+        assert!("20011302".parse::<SecDate>().is_err());
+        assert!("0000".parse::<MonthDay>().is_err());
+        assert!("2001-01-32".parse::<SecDate>().is_err());
+
+        // Non-ASCII values of the expected byte length
+        assert!("ÉÉÉÉ".parse::<SecDate>().is_err());
+        assert!("1É2".parse::<MonthDay>().is_err());
+        assert!("20010102ÉÉÉ".parse::<SecDateTime>().is_err());
+        assert!(matches!("É".parse::<SecDate>(), Err(ParseError::InvalidDate(_))));
+    }
+
+    #[test]
+    fn test_normalize_header_dates() {
+        let mut company = HashMap::new();
+        company.insert("fiscal-year-end".to_string(), MetadataValue::string("1231"));
+        let mut fields = HashMap::new();
+        fields.insert("filing-date".to_string(), MetadataValue::string("20010102"));
+        fields.insert("acceptance-datetime".to_string(), MetadataValue::string("20010102123456"));
+        fields.insert("sec-document".to_string(), MetadataValue::string("0000774352-01-000002.txt : 20010102"));
+        fields.insert("company-data".to_string(), MetadataValue::Object(company));
+
+        normalize_header_dates(&mut fields);
+
+        assert_eq!(fields["filing-date"].as_str(), Some("2001-01-02"));
+        assert_eq!(fields["acceptance-datetime"].as_str(), Some("2001-01-02T12:34:56"));
+        assert_eq!(fields["sec-document-filename"].as_str(), Some("0000774352-01-000002.txt"));
+        assert_eq!(fields["sec-document-date"].as_str(), Some("2001-01-02"));
+        assert!(!fields.contains_key("sec-document"));
+        assert_eq!(
+            fields["company-data"].as_object().unwrap()["fiscal-year-end"].as_str(),
+            Some("--12-31")
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Character encoding detection and decoding to UTF-8
+//!
+//! EDGAR never required UTF-8. Older filings are mostly Windows-1252 or
+//! Latin-1 (smart quotes, `§`, `½`), and HTML documents may declare their
+//! charset. Non-ASCII text that is valid UTF-8 is UTF-8, whatever it
+//! declares, since that is almost never true of single-byte text. Otherwise
+//! an XML declaration or HTML `charset` for Windows-1252 or Latin-1 is
+//! followed, and without one the text is Windows-1252, or Latin-1 when none
+//! of the bytes 0x80-0x9F that the two disagree on appear.
+
+use crate::types::{DocumentMetadata, ParsedSubmission};
+use memchr::memmem;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How many leading bytes are searched for a declared charset
+const DECLARATION_WINDOW: usize = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Encoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "windows-1252")]
+    Windows1252,
+    #[serde(rename = "iso-8859-1")]
+    Latin1,
+}
+
+/// Windows-1252 characters for bytes 0x80-0x9F. The five unassigned bytes
+/// map to the C1 control with the same value, as browsers do.
+const CP1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+impl Encoding {
+    /// Encoding of `content`: UTF-8 when non-ASCII bytes are valid UTF-8,
+    /// else the declared single-byte charset, else a guess from the bytes
+    pub fn detect(content: &[u8]) -> Self {
+        // Labels are overruled both ways: a UTF-8 label on invalid UTF-8 and
+        // a Windows-1252 label on valid non-ASCII UTF-8 are equally wrong
+        let valid_utf8 = std::str::from_utf8(content).is_ok();
+        if valid_utf8 && !content.is_ascii() {
+            return Encoding::Utf8;
+        }
+        match declared_encoding(content) {
+            Some(Encoding::Latin1) if has_c1_bytes(content) => Encoding::Windows1252,
+            Some(encoding @ (Encoding::Latin1 | Encoding::Windows1252)) => encoding,
+            _ if valid_utf8 => Encoding::Utf8,
+            _ if has_c1_bytes(content) => Encoding::Windows1252,
+            _ => Encoding::Latin1,
+        }
+    }
+
+    /// Encoding named by a charset label (`utf-8`, `cp1252`, `ISO-8859-1`)
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "unicode-1-1-utf-8" => Some(Encoding::Utf8),
+            "windows-1252" | "cp1252" | "x-cp1252" => Some(Encoding::Windows1252),
+            "iso-8859-1" | "iso8859-1" | "iso_8859-1" | "latin1" | "latin-1" | "l1" => Some(Encoding::Latin1),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Windows1252 => "windows-1252",
+            Encoding::Latin1 => "iso-8859-1",
+        }
+    }
+
+    /// Decode `bytes` to UTF-8, borrowing when they already are.
+    ///
+    /// Invalid sequences in UTF-8 input are read as Windows-1252 rather
+    /// than replaced with U+FFFD, so a stray `0x93` becomes `“`.
+    pub fn decode(self, bytes: &[u8]) -> Cow<'_, str> {
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            if self == Encoding::Utf8 || text.is_ascii() {
+                return Cow::Borrowed(text);
+            }
+        }
+        match self {
+            Encoding::Utf8 => {
+                let mut text = String::with_capacity(bytes.len());
+                for chunk in bytes.utf8_chunks() {
+                    text.push_str(chunk.valid());
+                    text.extend(chunk.invalid().iter().map(|&b| cp1252_char(b)));
+                }
+                Cow::Owned(text)
+            }
+            Encoding::Windows1252 => Cow::Owned(bytes.iter().map(|&b| cp1252_char(b)).collect()),
+            Encoding::Latin1 => Cow::Owned(bytes.iter().map(|&b| char::from(b)).collect()),
+        }
+    }
+}
+
+/// Detect the encoding of `content` and decode it to UTF-8
+pub fn decode_text(content: &[u8]) -> (Cow<'_, str>, Encoding) {
+    let encoding = Encoding::detect(content);
+    (encoding.decode(content), encoding)
+}
+
+fn cp1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => CP1252_HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+fn has_c1_bytes(content: &[u8]) -> bool {
+    content.iter().any(|b| (0x80..=0x9F).contains(b))
+}
+
+/// Charset from an XML declaration or an HTML `charset=` near the start
+fn declared_encoding(content: &[u8]) -> Option<Encoding> {
+    let head = content[..content.len().min(DECLARATION_WINDOW)].to_ascii_lowercase();
+
+    if let Some(start) = memmem::find(&head, b"<?xml") {
+        let declaration = &head[start..];
+        let declaration = &declaration[..memchr::memchr(b'>', declaration).unwrap_or(declaration.len())];
+        if let Some(pos) = memmem::find(declaration, b"encoding=") {
+            return Encoding::from_label(&read_label(&declaration[pos + b"encoding=".len()..]));
+        }
+    }
+    let pos = memmem::find(&head, b"charset=")?;
+    Encoding::from_label(&read_label(&head[pos + b"charset=".len()..]))
+}
+
+/// The label after `charset=` or `encoding=`, without quotes
+fn read_label(rest: &[u8]) -> String {
+    rest.iter()
+        .skip_while(|&&b| b == b'"' || b == b'\'' || b.is_ascii_whitespace())
+        .take_while(|&&b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+        .map(|&b| char::from(b))
+        .collect()
+}
+
+impl ParsedSubmission {
+    /// UTF-8 view of every kept document whose encoding was detected while
+    /// parsing (see `ParseOptions::detect_encoding`). UU-encoded documents
+    /// have none and are skipped.
+    pub fn documents_as_utf8(&self) -> impl Iterator<Item = (&DocumentMetadata, Cow<'_, str>)> {
+        self.documents_with_metadata()
+            .filter_map(|(meta, content)| Some((meta, meta.encoding?.decode(content))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(Encoding::detect("Section § 12 “quoted”".as_bytes()), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"\x93quoted\x94 and \xa7 12"), Encoding::Windows1252);
+        assert_eq!(Encoding::detect(b"Section \xa7 12, \xbd share"), Encoding::Latin1);
+
+        // Single-byte declarations are followed when the bytes are not UTF-8
+        let html = b"<html><head><meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1252\">\xa7</head>";
+        assert_eq!(Encoding::detect(html), Encoding::Windows1252);
+        let xml = b"<XML>\n<?xml version=\"1.0\" encoding='ISO-8859-1'?>\n<a>\xe9</a>";
+        assert_eq!(Encoding::detect(xml), Encoding::Latin1);
+        let mislabeled = b"<meta charset=\"utf-8\"><p>\x93quoted\x94</p>";
+        assert_eq!(Encoding::detect(mislabeled), Encoding::Windows1252);
+        let latin1_with_quotes = b"<meta charset=iso-8859-1><p>\x93quoted\x94</p>";
+        assert_eq!(Encoding::detect(latin1_with_quotes), Encoding::Windows1252);
+        let utf8_labeled_1252 = "<meta charset=windows-1252><p>“quoted” §</p>";
+        assert_eq!(Encoding::detect(utf8_labeled_1252.as_bytes()), Encoding::Utf8);
+        // ASCII fits every label
+        assert_eq!(Encoding::detect(b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><a/>"), Encoding::Latin1);
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(Encoding::Windows1252.decode(b"\x93Net\x94 \x96 \x80 5 \xa7"), "“Net” – € 5 §");
+        assert_eq!(Encoding::Latin1.decode(b"caf\xe9"), "café");
+        assert_eq!(Encoding::Utf8.decode(b"d\xc3\xa9j\xe0 vu \x93"), "déjà vu “");
+        assert!(matches!(Encoding::Latin1.decode(b"plain"), Cow::Borrowed("plain")));
+
+        let (text, encoding) = decode_text(b"It\x92s");
+        assert_eq!((text.as_ref(), encoding), ("It’s", Encoding::Windows1252));
+    }
+
+    #[test]
+    fn test_documents_as_utf8() {
+        let submission = b"<SEC-HEADER>\nCOMPANY CONFORMED NAME:\tSOCI\xc9T\xc9 G\xc9N\xc9RALE\n</SEC-HEADER>\n\
+<DOCUMENT>\n<TYPE>10-K\n<SEQUENCE>1\n<FILENAME>d.txt\n<TEXT>\n\x93Annual\x94 report\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>GRAPHIC\n<SEQUENCE>2\n<FILENAME>logo.jpg\n<TEXT>\nbegin 644 logo.jpg\n#86)C\n`\nend\n</TEXT>\n</DOCUMENT>\n";
+
+        let parsed = parse_sgml(submission, ParseOptions::new()).unwrap();
+        assert!(parsed.metadata.documents.iter().all(|meta| meta.encoding.is_none()));
+        assert_eq!(parsed.documents_as_utf8().count(), 0);
+
+        let parsed = parse_sgml(submission, ParseOptions::new().with_encoding_detection()).unwrap();
+        let encodings: Vec<_> = parsed.metadata.documents.iter().map(|meta| meta.encoding).collect();
+        assert_eq!(encodings, vec![Some(Encoding::Windows1252), None]);
+        let texts: Vec<_> = parsed.documents_as_utf8().map(|(_, text)| text.into_owned()).collect();
+        assert_eq!(texts, vec!["“Annual” report"]);
+
+        let json = serde_json::to_value(&parsed.metadata).unwrap();
+        assert_eq!(json["documents"][0]["secsgml_encoding"], "windows-1252");
+        assert!(json.to_string().contains("SOCIÉTÉ GÉNÉRALE"));
+    }
+}
@@ -0,0 +1,279 @@
+//! Typed view of the company and person sections of a header
+//!
+//! Filers, reporting owners, issuers, subject companies and filed-by
+//! entities all share the same layout: a company or owner data block,
+//! filing values, addresses and former names. Sections are read whether
+//! keys were standardized or not, and whether a section occurs once
+//! (object) or several times (list).
+
+use crate::header_mappings::{standardize_key, transform_value};
+use crate::ids::{Cik, FileNumber, FilmNumber};
+use crate::types::MetadataValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A company or person named in a header section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Entity {
+    pub cik: Option<Cik>,
+    pub conformed_name: Option<String>,
+    pub assigned_sic: Option<u16>,
+    pub organization_name: Option<String>,
+    /// IRS number, or EIN in newer headers
+    pub irs_number: Option<String>,
+    pub state_of_incorporation: Option<String>,
+    pub fiscal_year_end: Option<String>,
+    pub filing_values: Vec<FilingValues>,
+    pub business_address: Option<Address>,
+    pub mail_address: Option<Address>,
+    pub former_names: Vec<FormerName>,
+}
+
+/// Form, act and numbers under which an entity filed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FilingValues {
+    pub form_type: Option<String>,
+    /// Two-digit act (`33`, `34`, `40`)
+    pub act: Option<String>,
+    pub file_number: Option<FileNumber>,
+    pub film_number: Option<FilmNumber>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Address {
+    pub street1: Option<String>,
+    pub street2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub phone: Option<String>,
+}
+
+/// A previous name of the entity
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FormerName {
+    pub conformed_name: Option<String>,
+    pub date_changed: Option<String>,
+}
+
+impl Entity {
+    /// Read an entity from a section such as `filer` or `reporting-owner`
+    pub fn from_section(section: &HashMap<String, MetadataValue>) -> Self {
+        let data = section_list(section, "company-data")
+            .into_iter()
+            .chain(section_list(section, "owner-data"))
+            .next();
+
+        let mut entity = Entity {
+            filing_values: section_list(section, "filing-values")
+                .into_iter()
+                .map(FilingValues::from_section)
+                .collect(),
+            business_address: section_list(section, "business-address")
+                .into_iter()
+                .next()
+                .map(Address::from_section),
+            mail_address: section_list(section, "mail-address")
+                .into_iter()
+                .next()
+                .map(Address::from_section),
+            former_names: section_list(section, "former-company")
+                .into_iter()
+                .chain(section_list(section, "former-name"))
+                .map(FormerName::from_section)
+                .collect(),
+            ..Default::default()
+        };
+
+        if let Some(data) = data {
+            entity.cik = text(data, "cik").and_then(|s| s.parse().ok());
+            entity.conformed_name = text(data, "conformed-name");
+            entity.assigned_sic = text(data, "assigned-sic").and_then(|s| s.parse().ok());
+            entity.organization_name = text(data, "organization-name");
+            entity.irs_number = text(data, "irs-number").or_else(|| text(data, "ein"));
+            entity.state_of_incorporation = text(data, "state-of-incorporation");
+            entity.fiscal_year_end = text(data, "fiscal-year-end");
+        }
+
+        entity
+    }
+}
+
+impl FilingValues {
+    fn from_section(section: &HashMap<String, MetadataValue>) -> Self {
+        FilingValues {
+            form_type: text(section, "form-type"),
+            act: text(section, "act"),
+            file_number: text(section, "file-number").and_then(|s| s.parse().ok()),
+            film_number: text(section, "film-number").and_then(|s| s.parse().ok()),
+        }
+    }
+}
+
+impl Address {
+    fn from_section(section: &HashMap<String, MetadataValue>) -> Self {
+        Address {
+            street1: text(section, "street1"),
+            street2: text(section, "street2"),
+            city: text(section, "city"),
+            state: text(section, "state"),
+            zip: text(section, "zip"),
+            phone: text(section, "phone"),
+        }
+    }
+}
+
+impl FormerName {
+    fn from_section(section: &HashMap<String, MetadataValue>) -> Self {
+        FormerName {
+            conformed_name: text(section, "former-conformed-name"),
+            date_changed: text(section, "date-changed"),
+        }
+    }
+}
+
+/// Find a field by its standardized key, whatever spelling the header used
+pub(crate) fn lookup<'a>(
+    fields: &'a HashMap<String, MetadataValue>,
+    standard_key: &str,
+) -> Option<(&'a str, &'a MetadataValue)> {
+    fields
+        .get_key_value(standard_key)
+        .or_else(|| fields.iter().find(|(key, _)| standardize_key(key) == standard_key))
+        .map(|(key, value)| (key.as_str(), value))
+}
+
+/// Every occurrence of a section, whether it appeared once or several times
+pub(crate) fn section_list<'a>(
+    fields: &'a HashMap<String, MetadataValue>,
+    standard_key: &str,
+) -> Vec<&'a HashMap<String, MetadataValue>> {
+    match lookup(fields, standard_key) {
+        Some((_, MetadataValue::Object(obj))) => vec![obj],
+        Some((_, MetadataValue::List(items))) => {
+            items.iter().filter_map(MetadataValue::as_object).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Every non-empty string value of a field, whether it appeared once or several times
+pub(crate) fn text_list(fields: &HashMap<String, MetadataValue>, standard_key: &str) -> Vec<String> {
+    let Some((key, value)) = lookup(fields, standard_key) else {
+        return Vec::new();
+    };
+    let values = match value {
+        MetadataValue::String(s) => vec![s.as_str()],
+        MetadataValue::List(items) => items.iter().filter_map(MetadataValue::as_str).collect(),
+        MetadataValue::Object(_) => Vec::new(),
+    };
+    values
+        .into_iter()
+        .map(|value| transform_value(key, value.trim()))
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// A non-empty string field, with the standard value transform applied
+/// when keys were not standardized
+pub(crate) fn text(fields: &HashMap<String, MetadataValue>, standard_key: &str) -> Option<String> {
+    text_list(fields, standard_key).into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_sgml, ParseOptions};
+
+    const FORM_4: &[u8] = b"<SEC-DOCUMENT>0001234567-05-000001.txt : 20050103
+<SEC-HEADER>0001234567-05-000001.hdr.sgml : 20050103
+ACCESSION NUMBER:\t\t0001234567-05-000001
+CONFORMED SUBMISSION TYPE:\t4
+PUBLIC DOCUMENT COUNT:\t\t1
+FILED AS OF DATE:\t\t20050103
+
+ISSUER:\t\t
+
+\tCOMPANY DATA:\t
+\t\tCOMPANY CONFORMED NAME:\t\t\tWIDGETS INC
+\t\tCENTRAL INDEX KEY:\t\t\t0000012345
+\t\tSTANDARD INDUSTRIAL CLASSIFICATION:\tSERVICES-PREPACKAGED SOFTWARE [7372]
+
+\tBUSINESS ADDRESS:\t
+\t\tSTREET 1:\t\t1 MAIN ST
+\t\tCITY:\t\t\tNEW YORK
+\t\tSTATE:\t\t\tNY
+\t\tBUSINESS PHONE:\t\t2125550100
+
+REPORTING-OWNER:\t
+
+\tOWNER DATA:\t
+\t\tCOMPANY CONFORMED NAME:\t\t\tDOE JOHN
+\t\tCENTRAL INDEX KEY:\t\t\t0001234567
+
+\tFILING VALUES:
+\t\tFORM TYPE:\t\t4
+\t\tSEC ACT:\t\t1934 Act
+\t\tSEC FILE NUMBER:\t000-12345
+\t\tFILM NUMBER:\t\t05512345
+
+\tMAIL ADDRESS:\t
+\t\tSTREET 1:\t\tPO BOX 1
+\t\tCITY:\t\t\tNEW YORK
+
+\tFORMER NAME:\t
+\t\tFORMER CONFORMED NAME:\tDOE JOHNNY
+\t\tDATE OF NAME CHANGE:\t20000101
+
+REPORTING-OWNER:\t
+
+\tOWNER DATA:\t
+\t\tCOMPANY CONFORMED NAME:\t\t\tDOE JANE
+\t\tCENTRAL INDEX KEY:\t\t\t0001234568
+</SEC-HEADER>
+";
+
+    #[test]
+    fn test_ownership_entities() {
+        for options in [ParseOptions::new(), ParseOptions::preserve_original()] {
+            let parsed = parse_sgml(FORM_4, options).unwrap();
+            let metadata = &parsed.metadata;
+
+            let issuers = metadata.issuers();
+            assert_eq!(issuers.len(), 1);
+            assert_eq!(issuers[0].conformed_name.as_deref(), Some("WIDGETS INC"));
+            assert_eq!(issuers[0].assigned_sic, Some(7372));
+            let address = issuers[0].business_address.as_ref().unwrap();
+            assert_eq!(address.phone.as_deref(), Some("2125550100"));
+
+            let owners = metadata.reporting_owners();
+            assert_eq!(owners.len(), 2);
+            assert_eq!(owners[0].cik.unwrap().to_string(), "0001234567");
+            assert_eq!(owners[0].filing_values[0].act.as_deref(), Some("34"));
+            assert_eq!(
+                owners[0].filing_values[0].file_number.as_ref().unwrap().as_str(),
+                "000-12345"
+            );
+            assert_eq!(owners[0].former_names[0].conformed_name.as_deref(), Some("DOE JOHNNY"));
+            assert_eq!(owners[0].mail_address.as_ref().unwrap().street1.as_deref(), Some("PO BOX 1"));
+            assert_eq!(owners[1].conformed_name.as_deref(), Some("DOE JANE"));
+
+            assert!(metadata.filers().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_group_members() {
+        let data = b"<SUBMISSION>\n<TYPE>SC 13D\n<GROUP-MEMBERS>JANE DOE\n<GROUP-MEMBERS>JOHN DOE\n\
+<SUBJECT-COMPANY>\n<COMPANY-DATA>\n<CIK>0000012345\n</COMPANY-DATA>\n</SUBJECT-COMPANY>\n\
+<FILED-BY>\n<COMPANY-DATA>\n<CIK>0001234567\n</COMPANY-DATA>\n</FILED-BY>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+
+        assert_eq!(parsed.metadata.group_members(), vec!["JANE DOE", "JOHN DOE"]);
+        assert_eq!(parsed.metadata.subject_companies()[0].cik.unwrap().value(), 12345);
+        assert_eq!(parsed.metadata.filed_by()[0].cik.unwrap().value(), 1234567);
+    }
+}
@@ -16,6 +16,18 @@ pub enum ParseError {
     #[error("UU-decode error: {0}")]
     UuDecodeError(String),
 
+    #[error("Invalid identifier: {0}")]
+    InvalidIdentifier(String),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Unsupported compression: enable the `{0}` feature")]
+    UnsupportedCompression(&'static str),
+
+    #[error("XML error: {0}")]
+    XmlError(String),
+
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
 }
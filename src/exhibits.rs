@@ -0,0 +1,163 @@
+//! Primary document detection and exhibit classification
+//!
+//! Document types are the form type for the main document and
+//! `EX-<number>` for exhibits, with XBRL, graphics and archives added by
+//! EDGAR. Exhibit numbers follow Item 601 of Regulation S-K, so the part
+//! before the first `.` decides the kind.
+
+use crate::types::{DocumentMetadata, ParsedSubmission};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExhibitKind {
+    /// The form itself
+    Primary,
+    /// EX-10
+    MaterialContract,
+    /// EX-21
+    Subsidiaries,
+    /// EX-23
+    Consent,
+    /// EX-24
+    PowerOfAttorney,
+    /// EX-31 and EX-32 officer certifications
+    Certification,
+    /// EX-27
+    FinancialDataSchedule,
+    /// EX-99, mostly press releases furnished with an 8-K
+    PressRelease,
+    /// EX-101.INS, or the instance EDGAR extracts from inline XBRL
+    XbrlInstance,
+    /// EX-101.SCH
+    XbrlSchema,
+    /// EX-101.CAL, .DEF, .LAB and .PRE
+    XbrlLinkbase,
+    /// EX-104 cover page inline XBRL
+    CoverPage,
+    /// Images
+    Graphic,
+    /// ZIP and other packaged files
+    Archive,
+    /// CORRESP letters
+    Correspondence,
+    Other,
+}
+
+impl ExhibitKind {
+    /// Kind of a non-primary document from its type and filename
+    pub fn classify(meta: &DocumentMetadata) -> Self {
+        let doc_type = meta.doc_type().unwrap_or_default().trim().to_ascii_uppercase();
+        let filename = meta.filename().unwrap_or_default().to_ascii_lowercase();
+
+        if let Some(exhibit) = doc_type.strip_prefix("EX-") {
+            let (number, suffix) = exhibit.split_once('.').unwrap_or((exhibit, ""));
+            return match number {
+                "10" => ExhibitKind::MaterialContract,
+                "21" => ExhibitKind::Subsidiaries,
+                "23" => ExhibitKind::Consent,
+                "24" => ExhibitKind::PowerOfAttorney,
+                "27" => ExhibitKind::FinancialDataSchedule,
+                "31" | "32" => ExhibitKind::Certification,
+                "99" => ExhibitKind::PressRelease,
+                "101" => match suffix {
+                    "INS" => ExhibitKind::XbrlInstance,
+                    "SCH" => ExhibitKind::XbrlSchema,
+                    "CAL" | "DEF" | "LAB" | "PRE" => ExhibitKind::XbrlLinkbase,
+                    _ => ExhibitKind::Other,
+                },
+                "104" => ExhibitKind::CoverPage,
+                _ => ExhibitKind::Other,
+            };
+        }
+
+        match doc_type.as_str() {
+            "GRAPHIC" => ExhibitKind::Graphic,
+            "ZIP" => ExhibitKind::Archive,
+            "CORRESP" => ExhibitKind::Correspondence,
+            "XML" if filename.ends_with("_htm.xml") => ExhibitKind::XbrlInstance,
+            _ if [".jpg", ".jpeg", ".gif", ".png"].iter().any(|ext| filename.ends_with(ext)) => {
+                ExhibitKind::Graphic
+            }
+            _ if filename.ends_with(".zip") => ExhibitKind::Archive,
+            _ => ExhibitKind::Other,
+        }
+    }
+}
+
+impl ParsedSubmission {
+    /// Metadata of the main document: the first document whose type is the
+    /// submission type, else the document with sequence 1
+    pub fn primary_metadata(&self) -> Option<&DocumentMetadata> {
+        let form = self.metadata.submission_type();
+        let documents = &self.metadata.documents;
+        documents
+            .iter()
+            .find(|meta| {
+                form.is_some_and(|form| meta.doc_type().is_some_and(|t| t.eq_ignore_ascii_case(form)))
+            })
+            .or_else(|| documents.iter().find(|meta| meta.sequence() == Some("1")))
+    }
+
+    /// The main document and its content, if its content was kept
+    pub fn primary_document(&self) -> Option<(&DocumentMetadata, &[u8])> {
+        let primary = self.primary_metadata()?;
+        self.documents_with_metadata().find(|(meta, _)| std::ptr::eq(*meta, primary))
+    }
+
+    /// Kept documents grouped by kind, the primary document under
+    /// [`ExhibitKind::Primary`]
+    pub fn exhibits_by_kind(&self) -> BTreeMap<ExhibitKind, Vec<(&DocumentMetadata, &[u8])>> {
+        let primary = self.primary_metadata();
+        let mut kinds: BTreeMap<ExhibitKind, Vec<_>> = BTreeMap::new();
+        for (meta, content) in self.documents_with_metadata() {
+            let kind = if primary.is_some_and(|primary| std::ptr::eq(meta, primary)) {
+                ExhibitKind::Primary
+            } else {
+                ExhibitKind::classify(meta)
+            };
+            kinds.entry(kind).or_default().push((meta, content));
+        }
+        kinds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    const EIGHT_K: &[u8] = b"<SUBMISSION>\n<TYPE>8-K\n\
+<DOCUMENT>\n<TYPE>EX-99.1\n<SEQUENCE>2\n<FILENAME>ex99-1.htm\n<TEXT>\nPress release\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>8-K\n<SEQUENCE>1\n<FILENAME>form8-k.htm\n<TEXT>\nCurrent report\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-99.2\n<SEQUENCE>3\n<FILENAME>ex99-2.htm\n<TEXT>\nSlides\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-101.LAB\n<SEQUENCE>4\n<FILENAME>x-lab.xml\n<TEXT>\n<XBRL>\n<linkbase/>\n</XBRL>\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>GRAPHIC\n<SEQUENCE>5\n<FILENAME>logo.jpg\n<TEXT>\nbegin 644 logo.jpg\n`\nend\n</TEXT>\n</DOCUMENT>\n";
+
+    #[test]
+    fn test_primary_document() {
+        let parsed = parse_sgml(EIGHT_K, ParseOptions::new()).unwrap();
+        let (meta, content) = parsed.primary_document().unwrap();
+        assert_eq!(meta.doc_type(), Some("8-K"));
+        assert_eq!(content, b"Current report");
+
+        let filtered = parse_sgml(EIGHT_K, ParseOptions::new().with_filter(vec!["EX-99.1".to_string()])).unwrap();
+        assert!(filtered.primary_document().is_none());
+    }
+
+    #[test]
+    fn test_exhibits_by_kind() {
+        let parsed = parse_sgml(EIGHT_K, ParseOptions::new()).unwrap();
+        let kinds = parsed.exhibits_by_kind();
+
+        let sequences = |kind: ExhibitKind| -> Vec<&str> {
+            kinds[&kind].iter().filter_map(|(meta, _)| meta.sequence()).collect()
+        };
+        assert_eq!(sequences(ExhibitKind::Primary), vec!["1"]);
+        assert_eq!(sequences(ExhibitKind::PressRelease), vec!["2", "3"]);
+        assert_eq!(sequences(ExhibitKind::XbrlLinkbase), vec!["4"]);
+        assert_eq!(sequences(ExhibitKind::Graphic), vec!["5"]);
+        assert!(!kinds.contains_key(&ExhibitKind::Certification));
+    }
+}
@@ -0,0 +1,218 @@
+//! EDGAR daily feed archive ingestion
+//!
+//! The daily feed is a gzip-compressed tar of `.nc` submission files.
+//! `FeedReader` walks the archive member by member so a whole day of
+//! filings never has to be held in memory at once.
+
+use crate::compression::{decompress, decompressing_reader};
+use crate::error::Result;
+use crate::parse::parse_sgml;
+use crate::types::{ParseOptions, ParsedSubmission};
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Number of members read ahead before each parallel parse
+const PAR_BATCH_SIZE: usize = 256;
+
+/// A single submission file read from a feed archive
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    /// Member path inside the archive (e.g. `0000950123-01-000001.nc`)
+    pub name: String,
+    /// Raw SGML bytes of the member
+    pub data: Vec<u8>,
+}
+
+impl FeedEntry {
+    /// Parse the member's SGML, decompressing it first if needed
+    pub fn parse(&self, options: ParseOptions) -> Result<ParsedSubmission> {
+        parse_sgml(&decompress(&self.data)?, options)
+    }
+}
+
+/// Reader over the submission files of a feed archive
+pub struct FeedReader<R: Read> {
+    archive: tar::Archive<R>,
+    options: ParseOptions,
+}
+
+impl FeedReader<Box<dyn Read + Send>> {
+    /// Open a feed archive, decompressing it if it is compressed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(decompressing_reader(File::open(path)?)?))
+    }
+}
+
+impl<R: Read> FeedReader<R> {
+    /// Wrap an uncompressed tar stream
+    pub fn new(reader: R) -> Self {
+        Self {
+            archive: tar::Archive::new(reader),
+            options: ParseOptions::new(),
+        }
+    }
+
+    /// Options used by `submissions` and `par_for_each`
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Iterate over the regular-file members of the archive
+    pub fn entries(&mut self) -> Result<FeedEntries<'_, R>> {
+        Ok(FeedEntries {
+            entries: self.archive.entries()?,
+        })
+    }
+
+    /// Iterate over members, parsing each one
+    pub fn submissions(
+        &mut self,
+    ) -> Result<impl Iterator<Item = Result<(String, Result<ParsedSubmission>)>> + '_> {
+        let options = self.options.clone();
+        Ok(self.entries()?.map(move |entry| {
+            entry.map(|entry| {
+                let parsed = entry.parse(options.clone());
+                (entry.name, parsed)
+            })
+        }))
+    }
+
+    /// Parse all members in parallel, calling `f` with each member name and result.
+    ///
+    /// Members are read sequentially (tar is a stream) and parsed in batches.
+    /// Calls to `f` are not ordered.
+    pub fn par_for_each<F>(mut self, f: F) -> Result<()>
+    where
+        F: Fn(&str, Result<ParsedSubmission>) + Sync + Send,
+    {
+        let options = self.options.clone();
+        let mut batch: Vec<FeedEntry> = Vec::with_capacity(PAR_BATCH_SIZE);
+
+        let parse_batch = |batch: &mut Vec<FeedEntry>| {
+            batch
+                .par_drain(..)
+                .for_each(|entry| f(&entry.name, entry.parse(options.clone())));
+        };
+
+        for entry in self.entries()? {
+            batch.push(entry?);
+            if batch.len() == PAR_BATCH_SIZE {
+                parse_batch(&mut batch);
+            }
+        }
+        parse_batch(&mut batch);
+
+        Ok(())
+    }
+}
+
+/// Most bytes reserved up front for a member, whatever its header claims
+const MAX_RESERVE: u64 = 64 << 20;
+
+/// Iterator over the members of a feed archive
+pub struct FeedEntries<'a, R: 'a + Read> {
+    entries: tar::Entries<'a, R>,
+}
+
+impl<'a, R: Read> Iterator for FeedEntries<'a, R> {
+    type Item = Result<FeedEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut entry = match self.entries.next()? {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let name = match entry.path() {
+                Ok(path) => path.to_string_lossy().into_owned(),
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            // The header size is only a hint; a corrupt one must not reserve gigabytes
+            let mut data = Vec::with_capacity(entry.size().min(MAX_RESERVE) as usize);
+            if let Err(e) = entry.read_to_end(&mut data) {
+                return Some(Err(e.into()));
+            }
+
+            return Some(Ok(FeedEntry { name, data }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    fn build_feed(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, data) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    const SUBMISSION: &[u8] = b"<SUBMISSION>\n<ACCESSION-NUMBER>0000774352-01-000002\n<TYPE>8-K\n\
+<DOCUMENT>\n<TYPE>8-K\n<SEQUENCE>1\n<TEXT>\nreport\n</TEXT>\n</DOCUMENT>\n</SUBMISSION>\n";
+
+    #[test]
+    fn test_feed_submissions() {
+        let feed = build_feed(&[("a.nc", SUBMISSION), ("b.nc", SUBMISSION)]);
+        let mut reader = FeedReader::new(GzDecoder::new(Cursor::new(feed)));
+
+        let parsed: Vec<_> = reader
+            .submissions()
+            .unwrap()
+            .map(|item| item.unwrap())
+            .collect();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, "a.nc");
+        assert_eq!(parsed[1].1.as_ref().unwrap().documents[0], b"report");
+    }
+
+    #[test]
+    fn test_feed_par_for_each() {
+        let members: Vec<(String, &[u8])> = (0..300).map(|i| (format!("{i}.nc"), SUBMISSION)).collect();
+        let members: Vec<(&str, &[u8])> = members.iter().map(|(n, d)| (n.as_str(), *d)).collect();
+        let feed = build_feed(&members);
+
+        let names = Mutex::new(Vec::new());
+        FeedReader::new(GzDecoder::new(Cursor::new(feed)))
+            .par_for_each(|name, parsed| {
+                assert!(parsed.is_ok());
+                names.lock().unwrap().push(name.to_string());
+            })
+            .unwrap();
+
+        assert_eq!(names.into_inner().unwrap().len(), 300);
+    }
+
+    #[test]
+    fn test_feed_open() {
+        let path = std::env::temp_dir().join(format!("secsgml-feed-{}.nc.tar.gz", std::process::id()));
+        std::fs::write(&path, build_feed(&[("a.nc", SUBMISSION)])).unwrap();
+
+        let mut reader = FeedReader::open(&path).unwrap();
+        let names: Vec<String> = reader.entries().unwrap().map(|entry| entry.unwrap().name).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(names, vec!["a.nc"]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Document selection
+//!
+//! Filters are checked against the document metadata and the raw text
+//! between `<TEXT>` and `</TEXT>`, before UU-decoding and cleaning, so
+//! skipped documents cost no more than finding their boundaries.
+
+use crate::types::DocumentMetadata;
+use crate::uudecode::is_uuencoded;
+use std::ops::RangeInclusive;
+
+/// Which documents of a submission to keep. Every set condition must hold;
+/// the default keeps everything.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentFilter {
+    /// Type patterns to keep (`10-K`, `EX-99*`, `EX-101.???`), matched
+    /// case-insensitively. Empty keeps all types.
+    pub include_types: Vec<String>,
+    /// Type patterns to drop, even when included
+    pub exclude_types: Vec<String>,
+    /// Sequence numbers to keep
+    pub sequences: Option<RangeInclusive<u32>>,
+    /// Filename extensions to keep, without the dot (`htm`, `pdf`)
+    pub extensions: Vec<String>,
+    /// Content types to keep. Empty keeps all.
+    pub content_types: Vec<ContentType>,
+    /// Smallest raw (still encoded) content size to keep, in bytes
+    pub min_size: Option<usize>,
+    /// Largest raw (still encoded) content size to keep, in bytes
+    pub max_size: Option<usize>,
+}
+
+/// What a document's content holds, judged from its wrapper tag,
+/// encoding and filename
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentType {
+    Text,
+    Html,
+    /// `<XML>` wrapped documents
+    Xml,
+    /// `<XBRL>` wrapped documents
+    Xbrl,
+    /// `<PDF>` wrapped documents
+    Pdf,
+    /// UU-encoded files (images, archives, spreadsheets)
+    Binary,
+}
+
+impl ContentType {
+    fn detect(meta: &DocumentMetadata, raw: &[u8]) -> Self {
+        let start = raw.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(raw.len());
+        let content = &raw[start..];
+        if content.starts_with(b"<PDF>") {
+            ContentType::Pdf
+        } else if content.starts_with(b"<XBRL>") {
+            ContentType::Xbrl
+        } else if content.starts_with(b"<XML>") {
+            ContentType::Xml
+        } else if is_uuencoded(content) {
+            ContentType::Binary
+        } else if extension(meta).is_some_and(|ext| ext.eq_ignore_ascii_case("htm") || ext.eq_ignore_ascii_case("html")) {
+            ContentType::Html
+        } else {
+            ContentType::Text
+        }
+    }
+}
+
+impl DocumentFilter {
+    /// Keep only documents whose type matches one of `patterns`
+    pub fn types<S: Into<String>>(patterns: impl IntoIterator<Item = S>) -> Self {
+        DocumentFilter {
+            include_types: patterns.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Drop documents whose type matches one of `patterns`
+    pub fn exclude<S: Into<String>>(mut self, patterns: impl IntoIterator<Item = S>) -> Self {
+        self.exclude_types.extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether the document passes the filter
+    pub(crate) fn accepts(&self, meta: &DocumentMetadata, raw: &[u8]) -> bool {
+        let doc_type = meta.doc_type().unwrap_or_default();
+        if !self.include_types.is_empty() && !self.include_types.iter().any(|p| glob_match(p, doc_type)) {
+            return false;
+        }
+        if self.exclude_types.iter().any(|p| glob_match(p, doc_type)) {
+            return false;
+        }
+        if let Some(range) = &self.sequences {
+            let sequence = meta.sequence().and_then(|s| s.trim().parse::<u32>().ok());
+            if !sequence.is_some_and(|s| range.contains(&s)) {
+                return false;
+            }
+        }
+        if !self.extensions.is_empty() {
+            let Some(ext) = extension(meta) else {
+                return false;
+            };
+            if !self.extensions.iter().any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext)) {
+                return false;
+            }
+        }
+        if self.min_size.is_some_and(|min| raw.len() < min) || self.max_size.is_some_and(|max| raw.len() > max) {
+            return false;
+        }
+        self.content_types.is_empty() || self.content_types.contains(&ContentType::detect(meta, raw))
+    }
+}
+
+fn extension(meta: &DocumentMetadata) -> Option<&str> {
+    meta.filename()?.trim().rsplit_once('.').map(|(_, ext)| ext)
+}
+
+/// Case-insensitive match with `*` (any run) and `?` (any one character)
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim().chars().map(|c| c.to_ascii_uppercase()).collect();
+    let text: Vec<char> = text.trim().chars().map(|c| c.to_ascii_uppercase()).collect();
+
+    // Greedy matching with backtracking to the last `*`
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    const SUBMISSION: &[u8] = b"<SUBMISSION>\n<TYPE>10-K\n\
+<DOCUMENT>\n<TYPE>10-K\n<SEQUENCE>1\n<FILENAME>form10-k.htm\n<TEXT>\n<html>Annual report</html>\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-99.1\n<SEQUENCE>2\n<FILENAME>ex99-1.htm\n<TEXT>\nPress release\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-99.2\n<SEQUENCE>3\n<FILENAME>ex99-2.txt\n<TEXT>\nSlides\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-101.INS\n<SEQUENCE>4\n<FILENAME>x.xml\n<TEXT>\n<XBRL>\n<xbrl/>\n</XBRL>\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>GRAPHIC\n<SEQUENCE>5\n<FILENAME>logo.jpg\n<TEXT>\nbegin 644 logo.jpg\n#86)C\n`\nend\n</TEXT>\n</DOCUMENT>\n";
+
+    fn kept(filter: DocumentFilter) -> Vec<String> {
+        let options = ParseOptions::new().with_document_filter(filter);
+        let parsed = parse_sgml(SUBMISSION, options).unwrap();
+        parsed
+            .documents_with_metadata()
+            .filter_map(|(meta, _)| meta.sequence().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("EX-99*", "EX-99.1"));
+        assert!(glob_match("ex-99*", "EX-99"));
+        assert!(glob_match("EX-101.???", "EX-101.INS"));
+        assert!(glob_match("*.INS", "EX-101.INS"));
+        assert!(glob_match("10-K", "10-K"));
+        assert!(!glob_match("10-K", "10-K/A"));
+        assert!(!glob_match("EX-99*", "EX-9"));
+    }
+
+    #[test]
+    fn test_document_filter() {
+        assert_eq!(kept(DocumentFilter::default()), vec!["1", "2", "3", "4", "5"]);
+        assert_eq!(kept(DocumentFilter::types(["EX-99*"])), vec!["2", "3"]);
+        assert_eq!(kept(DocumentFilter::types(["EX-*"]).exclude(["EX-101*"])), vec!["2", "3"]);
+        assert_eq!(
+            kept(DocumentFilter {
+                sequences: Some(2..=4),
+                extensions: vec!["htm".to_string(), ".XML".to_string()],
+                ..Default::default()
+            }),
+            vec!["2", "4"]
+        );
+        assert_eq!(
+            kept(DocumentFilter {
+                content_types: vec![ContentType::Binary, ContentType::Xbrl],
+                ..Default::default()
+            }),
+            vec!["4", "5"]
+        );
+        assert_eq!(
+            kept(DocumentFilter {
+                max_size: Some(10),
+                ..Default::default()
+            }),
+            vec!["3"]
+        );
+    }
+
+    #[test]
+    fn test_filtered_metadata_kept() {
+        let mut options = ParseOptions::new()
+            .with_filter(vec!["EX-99.1".to_string(), "GRAPHIC".to_string()])
+            .with_document_filter(DocumentFilter::types(["EX-99*", "GRAPHIC"]).exclude(["GRAPHIC"]));
+        options.keep_filtered_metadata = true;
+        let parsed = parse_sgml(SUBMISSION, options).unwrap();
+
+        assert_eq!(parsed.metadata.documents.len(), 5);
+        assert_eq!(parsed.documents, vec![b"Press release".to_vec()]);
+        let indices: Vec<Option<usize>> = parsed.metadata.documents.iter().map(|m| m.content_index).collect();
+        assert_eq!(indices, vec![None, Some(0), None, None, None]);
+        let sizes: Vec<(usize, Option<usize>)> =
+            parsed.metadata.documents.iter().map(|m| (m.size_bytes, m.raw_size_bytes)).collect();
+        assert_eq!(sizes[1], (13, None));
+        assert_eq!(sizes[0], (0, Some(28)));
+    }
+}
@@ -0,0 +1,321 @@
+//! EX-27 financial data schedules
+//!
+//! From 1997 to 2001 filings carried an EX-27 exhibit of tagged values:
+//! `<ARTICLE>` names the Regulation S-X article whose tag set is used,
+//! `<MULTIPLIER>` scales the amounts and each `<S>`/`<C>` column after the
+//! marker line reports one period, described by `<PERIOD-TYPE>`,
+//! `<FISCAL-YEAR-END>`, `<PERIOD-START>` and `<PERIOD-END>`.
+//!
+//! ```text
+//! <ARTICLE> 5
+//! <MULTIPLIER> 1,000
+//! <S>                             <C>             <C>
+//! <PERIOD-TYPE>                   YEAR            3-MOS
+//! <TOTAL-ASSETS>                  1,234           1,100
+//! <EPS-PRIMARY>                   (.12)           .45
+//! ```
+
+use crate::error::{ParseError, Result};
+use crate::types::ParsedSubmission;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document type prefix of financial data schedules (`EX-27`, `EX-27.1`, ...)
+pub const FINANCIAL_DATA_SCHEDULE: &str = "EX-27";
+
+/// Tags holding per-share amounts, rates and ratios, which the multiplier
+/// does not apply to
+const UNSCALED: &[&str] = &["EPS", "PER-SHARE", "RATIO", "EXCHANGE-RATE"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FinancialDataSchedule {
+    /// Regulation S-X article (`5` commercial, `6` investment company,
+    /// `7` insurance, `9` bank holding company, ...)
+    pub article: Option<String>,
+    pub legend: Option<String>,
+    /// Scale of the reported amounts, `1` when not given
+    pub multiplier: f64,
+    pub currency: Option<String>,
+    /// Series number and name of investment company schedules
+    pub series_number: Option<String>,
+    pub series_name: Option<String>,
+    /// One entry per reported period
+    pub columns: Vec<ScheduleColumn>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScheduleColumn {
+    pub period_type: Option<PeriodType>,
+    pub fiscal_year_end: Option<String>,
+    pub period_start: Option<String>,
+    pub period_end: Option<String>,
+    /// Numeric values by tag, multiplier applied
+    pub amounts: BTreeMap<String, f64>,
+    /// Values that are not numbers, by tag
+    pub text: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PeriodType {
+    Year,
+    /// `3-MOS`, `6-MOS`, `9-MOS`, ...
+    Months(u8),
+    Other(String),
+}
+
+/// Never fails: unknown period types are kept as [`PeriodType::Other`]
+impl std::str::FromStr for PeriodType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_uppercase();
+        if s == "YEAR" {
+            return Ok(PeriodType::Year);
+        }
+        let months = s
+            .strip_suffix("-MOS")
+            .or_else(|| s.strip_suffix("-MONTHS"))
+            .and_then(|n| n.parse().ok());
+        Ok(match months {
+            Some(12) => PeriodType::Year,
+            Some(months) => PeriodType::Months(months),
+            None => PeriodType::Other(s),
+        })
+    }
+}
+
+impl ScheduleColumn {
+    /// Amount of a tag such as `TOTAL-ASSETS`
+    pub fn amount(&self, tag: &str) -> Option<f64> {
+        self.amounts.get(tag).copied()
+    }
+}
+
+/// Parse the tagged values of an EX-27 document.
+///
+/// Values after the marker line are split on whitespace, one per column,
+/// so a blank cell in the middle of a row shifts the values after it left.
+pub fn parse_financial_data_schedule(data: &[u8]) -> Result<FinancialDataSchedule> {
+    let text = String::from_utf8_lossy(data);
+    let mut schedule = FinancialDataSchedule {
+        multiplier: 1.0,
+        ..Default::default()
+    };
+    let mut rows: Vec<(String, Vec<String>)> = Vec::new();
+    let mut legend: Option<Vec<String>> = None;
+    let mut found = false;
+
+    for line in text.lines() {
+        let line = strip_footnote_refs(line);
+        let trimmed = line.trim();
+
+        if let Some(lines) = legend.as_mut() {
+            match trimmed.strip_suffix("</LEGEND>") {
+                Some(last) => {
+                    lines.push(last.to_string());
+                    schedule.legend = Some(lines.join(" ").split_whitespace().collect::<Vec<_>>().join(" "));
+                    legend = None;
+                }
+                None => lines.push(trimmed.to_string()),
+            }
+            continue;
+        }
+
+        let Some((tag, value)) = split_tag(trimmed) else {
+            continue;
+        };
+        match tag {
+            "S" | "C" | "TABLE" | "/TABLE" | "FN" | "/FN" | "PAGE" => {}
+            "LEGEND" => match value.strip_suffix("</LEGEND>") {
+                Some(text) => schedule.legend = Some(text.trim().to_string()),
+                None => legend = Some(vec![value.to_string()]),
+            },
+            "ARTICLE" => schedule.article = Some(value.to_string()),
+            "MULTIPLIER" => {
+                schedule.multiplier = parse_amount(value).filter(|&m| m != 0.0).unwrap_or(1.0);
+            }
+            "CURRENCY" => schedule.currency = Some(value.to_string()),
+            "NUMBER" => schedule.series_number = Some(value.to_string()),
+            "NAME" => schedule.series_name = Some(value.to_string()),
+            "SERIES" => {}
+            _ => {
+                found = true;
+                rows.push((tag.to_string(), value.split_whitespace().map(str::to_string).collect()));
+            }
+        }
+    }
+
+    if !found {
+        return Err(ParseError::InvalidStructure(
+            "no financial data schedule tags found".into(),
+        ));
+    }
+
+    let width = rows.iter().map(|(_, values)| values.len()).max().unwrap_or(0);
+    schedule.columns = vec![ScheduleColumn::default(); width];
+    for (tag, values) in rows {
+        for (column, value) in schedule.columns.iter_mut().zip(values) {
+            match tag.as_str() {
+                "PERIOD-TYPE" => column.period_type = value.parse().ok(),
+                "FISCAL-YEAR-END" => column.fiscal_year_end = Some(value),
+                "PERIOD-START" => column.period_start = Some(value),
+                "PERIOD-END" => column.period_end = Some(value),
+                _ => match parse_amount(&value) {
+                    Some(amount) if UNSCALED.iter().any(|marker| tag.contains(marker)) => {
+                        column.amounts.insert(tag.clone(), amount);
+                    }
+                    Some(amount) => {
+                        column.amounts.insert(tag.clone(), amount * schedule.multiplier);
+                    }
+                    None => {
+                        column.text.insert(tag.clone(), value);
+                    }
+                },
+            }
+        }
+    }
+
+    Ok(schedule)
+}
+
+impl ParsedSubmission {
+    /// Parse the EX-27 documents of the submission.
+    ///
+    /// EX-27 documents without an `<ARTICLE>` tag, such as a cover letter
+    /// filed under that type, are skipped.
+    pub fn financial_data_schedules(&self) -> Result<Vec<FinancialDataSchedule>> {
+        self.documents_with_metadata()
+            .filter(|(meta, _)| {
+                meta.doc_type()
+                    .is_some_and(|t| t.to_ascii_uppercase().starts_with(FINANCIAL_DATA_SCHEDULE))
+            })
+            .filter(|(_, content)| memchr::memmem::find(content, b"<ARTICLE>").is_some())
+            .map(|(_, content)| parse_financial_data_schedule(content))
+            .collect()
+    }
+}
+
+/// `<TAG> value` into the tag name and the trimmed value
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('<')?;
+    let end = rest.find('>')?;
+    let tag = &rest[..end];
+    // Tag names are letters, digits, `-`, `/` and `&` (`PP&E`)
+    if tag.is_empty() || !tag.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'/' | b'&')) {
+        return None;
+    }
+    Some((tag, rest[end + 1..].trim()))
+}
+
+/// Drop `<F1>`-style footnote references from a line
+fn strip_footnote_refs(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("<F") {
+        let reference = rest[start + 2..]
+            .find('>')
+            .filter(|&end| end > 0 && rest[start + 2..start + 2 + end].bytes().all(|b| b.is_ascii_digit()));
+        out.push_str(&rest[..start]);
+        match reference {
+            Some(end) => rest = &rest[start + 3 + end..],
+            None => {
+                out.push_str("<F");
+                rest = &rest[start + 2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parse `1,234`, `(1,234)`, `-1,234` and `.45`
+fn parse_amount(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, value),
+    };
+    let cleaned: String = digits.chars().filter(|&c| c != ',' && c != '$').collect();
+    if !cleaned.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: f64 = cleaned.parse().ok()?;
+    Some(if negative { -amount } else { amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    const EX_27: &str = "<ARTICLE> 5
+<LEGEND>
+THIS SCHEDULE CONTAINS SUMMARY FINANCIAL INFORMATION EXTRACTED FROM
+THE ANNUAL REPORT.
+</LEGEND>
+<MULTIPLIER> 1,000
+<CURRENCY> U.S. DOLLARS
+<TABLE>
+<S>                             <C>             <C>
+<PERIOD-TYPE>                   YEAR            3-MOS
+<FISCAL-YEAR-END>               DEC-31-1997     DEC-31-1997
+<PERIOD-START>                  JAN-01-1997     OCT-01-1997
+<PERIOD-END>                    DEC-31-1997     DEC-31-1997
+<CASH>                          1,234           1,000
+<PP&E>                          5,000           4,900
+<TOTAL-ASSETS>                  98,765<F1>      97,000
+<NET-INCOME>                    (2,500)         300
+<EPS-PRIMARY>                   (.12)           .03
+<EPS-DILUTED>                   (.12)           .03
+</TABLE>
+<FN>
+<F1> Restated.
+</FN>
+";
+
+    #[test]
+    fn test_financial_data_schedule() {
+        let schedule = parse_financial_data_schedule(EX_27.as_bytes()).unwrap();
+        assert_eq!(schedule.article.as_deref(), Some("5"));
+        assert_eq!(
+            schedule.legend.as_deref(),
+            Some("THIS SCHEDULE CONTAINS SUMMARY FINANCIAL INFORMATION EXTRACTED FROM THE ANNUAL REPORT.")
+        );
+        assert_eq!(schedule.multiplier, 1000.0);
+        assert_eq!(schedule.currency.as_deref(), Some("U.S. DOLLARS"));
+        assert_eq!(schedule.columns.len(), 2);
+
+        let year = &schedule.columns[0];
+        assert_eq!(year.period_type, Some(PeriodType::Year));
+        assert_eq!(year.period_start.as_deref(), Some("JAN-01-1997"));
+        assert_eq!(year.amount("CASH"), Some(1_234_000.0));
+        assert_eq!(year.amount("PP&E"), Some(5_000_000.0));
+        assert_eq!(year.amount("TOTAL-ASSETS"), Some(98_765_000.0));
+        assert_eq!(year.amount("NET-INCOME"), Some(-2_500_000.0));
+        assert_eq!(year.amount("EPS-PRIMARY"), Some(-0.12));
+
+        let quarter = &schedule.columns[1];
+        assert_eq!(quarter.period_type, Some(PeriodType::Months(3)));
+        assert_eq!(quarter.amount("EPS-DILUTED"), Some(0.03));
+    }
+
+    #[test]
+    fn test_schedules_of_submission() {
+        let data = format!(
+            "<SUBMISSION>\n<TYPE>10-K\n\
+<DOCUMENT>\n<TYPE>10-K\n<SEQUENCE>1\n<TEXT>\nAnnual report\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-27\n<SEQUENCE>2\n<TEXT>\n{}</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-27.1\n<SEQUENCE>3\n<TEXT>\nSchedule filed on paper\n</TEXT>\n</DOCUMENT>\n",
+            EX_27
+        );
+        let parsed = parse_sgml(data.as_bytes(), ParseOptions::new()).unwrap();
+        let schedules = parsed.financial_data_schedules().unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].columns[0].amount("CASH"), Some(1_234_000.0));
+
+        assert!(parse_financial_data_schedule(b"Annual report").is_err());
+    }
+}
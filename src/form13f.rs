@@ -0,0 +1,308 @@
+//! Form 13F information table parsing
+//!
+//! Since 2013 the holdings of a 13F-HR are filed as an `INFORMATION TABLE`
+//! XML document. Earlier filings put a fixed-width text table in the main
+//! document; those rows are recovered heuristically by anchoring on the
+//! CUSIP column, so unusual layouts can yield fewer rows than the filing
+//! lists.
+
+use crate::error::{ParseError, Result};
+use crate::types::ParsedSubmission;
+use crate::xml::{child, children, number, parse_xml, text_at, xml_text};
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+
+/// Document type of the XML information table
+pub const INFORMATION_TABLE: &str = "INFORMATION TABLE";
+
+/// Document types that can carry a legacy text information table
+pub const FORM_13F_TYPES: &[&str] = &["13F-HR", "13F-HR/A"];
+
+/// One row of a 13F information table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Holding {
+    pub name_of_issuer: Option<String>,
+    pub title_of_class: Option<String>,
+    pub cusip: Option<String>,
+    pub figi: Option<String>,
+    /// Market value as reported: thousands of dollars before 2023, dollars after
+    pub value: Option<f64>,
+    pub shares_or_principal: Option<f64>,
+    /// `SH` (shares) or `PRN` (principal amount)
+    pub shares_or_principal_type: Option<String>,
+    /// `PUT` or `CALL`
+    pub put_call: Option<String>,
+    /// `SOLE`, `DFND` (shared-defined) or `OTR` (shared-other)
+    pub investment_discretion: Option<String>,
+    pub other_managers: Option<String>,
+    pub voting_sole: Option<f64>,
+    pub voting_shared: Option<f64>,
+    pub voting_none: Option<f64>,
+}
+
+/// Parse an XML `informationTable` document
+pub fn parse_information_table(data: &[u8]) -> Result<Vec<Holding>> {
+    let text = xml_text(data);
+    let xml = parse_xml(&text)?;
+    let root = xml.root_element();
+    if root.tag_name().name() != "informationTable" {
+        return Err(ParseError::XmlError(format!(
+            "expected informationTable, found {}",
+            root.tag_name().name()
+        )));
+    }
+
+    Ok(children(root, "infoTable").map(xml_holding).collect())
+}
+
+fn xml_holding(node: Node) -> Holding {
+    let amount = |path: &[&str]| text_at(node, path).and_then(|s| number(&s));
+    let voting = |column: &str| {
+        child(node, "votingAuthority")
+            .and_then(|v| text_at(v, &[column]))
+            .and_then(|s| number(&s))
+    };
+    Holding {
+        name_of_issuer: text_at(node, &["nameOfIssuer"]),
+        title_of_class: text_at(node, &["titleOfClass"]),
+        cusip: text_at(node, &["cusip"]).map(|s| s.to_ascii_uppercase()),
+        figi: text_at(node, &["figi"]),
+        value: amount(&["value"]),
+        shares_or_principal: amount(&["shrsOrPrnAmt", "sshPrnamt"]),
+        shares_or_principal_type: text_at(node, &["shrsOrPrnAmt", "sshPrnamtType"]),
+        put_call: text_at(node, &["putCall"]).map(|s| s.to_ascii_uppercase()),
+        investment_discretion: text_at(node, &["investmentDiscretion"]),
+        other_managers: text_at(node, &["otherManager"]),
+        voting_sole: voting("Sole"),
+        voting_shared: voting("Shared"),
+        voting_none: voting("None"),
+    }
+}
+
+/// Parse a legacy fixed-width text information table.
+///
+/// Rows are lines containing a CUSIP. Issuer and class are split at the
+/// `TITLE OF CLASS` header column when there is one, else at the last run
+/// of spaces. After the CUSIP come value, amount, `SH`/`PRN`, an optional
+/// `PUT`/`CALL`, discretion, other managers and the three voting columns.
+pub fn parse_text_information_table(data: &[u8]) -> Vec<Holding> {
+    let text = String::from_utf8_lossy(data);
+    let mut class_column = None;
+    let mut holdings = Vec::new();
+
+    for line in text.lines() {
+        let line = blank_table_tags(line);
+        if let Some(pos) = line.to_ascii_uppercase().find("TITLE OF CLASS") {
+            class_column = Some(pos);
+            continue;
+        }
+        if let Some(holding) = text_holding(&line, class_column) {
+            holdings.push(holding);
+        }
+    }
+
+    holdings
+}
+
+/// Replace `<S>`, `<C>` and table tags with spaces, keeping column positions
+fn blank_table_tags(line: &str) -> String {
+    let mut line = line.to_string();
+    for tag in ["<S>", "<C>", "<CAPTION>", "<TABLE>", "</TABLE>", "<s>", "<c>"] {
+        line = line.replace(tag, &" ".repeat(tag.len()));
+    }
+    line
+}
+
+fn text_holding(line: &str, class_column: Option<usize>) -> Option<Holding> {
+    // Find the CUSIP token and its byte offset
+    let (cusip_start, cusip) = line
+        .split_whitespace()
+        .map(|token| (token.as_ptr() as usize - line.as_ptr() as usize, token))
+        .find(|(_, token)| is_cusip(token))?;
+
+    let left = &line[..cusip_start];
+    let (issuer, class) = match class_column {
+        Some(col) if col < left.len() && left.is_char_boundary(col) => {
+            (left[..col].trim(), left[col..].trim())
+        }
+        _ => match left.trim_end().rfind("  ") {
+            Some(split) => (left[..split].trim(), left[split..].trim()),
+            None => (left.trim(), ""),
+        },
+    };
+    if issuer.is_empty() {
+        return None;
+    }
+
+    let mut tokens = line[cusip_start + cusip.len()..].split_whitespace().peekable();
+    let value = number(tokens.next()?)?;
+    let shares = tokens.next().and_then(number);
+
+    let mut take_if = |accepted: &[&str]| {
+        tokens
+            .next_if(|token| accepted.contains(&token.to_ascii_uppercase().as_str()))
+            .map(|token| token.to_ascii_uppercase())
+    };
+    let amount_type = take_if(&["SH", "PRN"]);
+    let put_call = take_if(&["PUT", "CALL"]);
+    let discretion = take_if(&[
+        "SOLE", "SHARED", "DEFINED", "DFND", "OTHER", "OTR", "SHARED-DEFINED", "SHARED-OTHER",
+    ]);
+
+    // The last three numbers are the voting columns; anything before them
+    // is the other-managers column
+    let rest: Vec<&str> = tokens.collect();
+    let (managers, voting) = if rest.len() >= 3 && rest[rest.len() - 3..].iter().all(|t| number(t).is_some()) {
+        rest.split_at(rest.len() - 3)
+    } else {
+        (&rest[..], &[][..])
+    };
+
+    Some(Holding {
+        name_of_issuer: Some(issuer.to_string()),
+        title_of_class: (!class.is_empty()).then(|| class.to_string()),
+        cusip: Some(cusip.to_ascii_uppercase()),
+        figi: None,
+        value: Some(value),
+        shares_or_principal: shares,
+        shares_or_principal_type: amount_type,
+        put_call,
+        investment_discretion: discretion,
+        other_managers: (!managers.is_empty()).then(|| managers.join(" ")),
+        voting_sole: voting.first().and_then(|t| number(t)),
+        voting_shared: voting.get(1).and_then(|t| number(t)),
+        voting_none: voting.get(2).and_then(|t| number(t)),
+    })
+}
+
+/// Nine alphanumeric characters with a valid check digit
+fn is_cusip(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    if bytes.len() != 9 || !bytes.iter().all(u8::is_ascii_alphanumeric) || !bytes[8].is_ascii_digit() {
+        return false;
+    }
+
+    let sum: u32 = bytes[..8]
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            let v = match b.to_ascii_uppercase() {
+                d @ b'0'..=b'9' => (d - b'0') as u32,
+                c => (c - b'A') as u32 + 10,
+            };
+            let v = if i % 2 == 1 { v * 2 } else { v };
+            v / 10 + v % 10
+        })
+        .sum();
+    (10 - sum % 10) % 10 == (bytes[8] - b'0') as u32
+}
+
+impl ParsedSubmission {
+    /// Holdings of a 13F-HR submission, from the XML information table or,
+    /// for older filings, the text table in the main document
+    pub fn information_table(&self) -> Result<Vec<Holding>> {
+        let mut holdings = Vec::new();
+        for (_, content) in self.documents_of_type(&[INFORMATION_TABLE]) {
+            holdings.extend(parse_information_table(content)?);
+        }
+        if holdings.is_empty() {
+            for (_, content) in self.documents_of_type(FORM_13F_TYPES) {
+                holdings.extend(parse_text_information_table(content));
+            }
+        }
+        Ok(holdings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    #[test]
+    fn test_xml_information_table() {
+        let data = br#"<SUBMISSION>
+<TYPE>13F-HR
+<DOCUMENT>
+<TYPE>13F-HR
+<TEXT>
+<XML>
+<edgarSubmission/>
+</XML>
+</TEXT>
+</DOCUMENT>
+<DOCUMENT>
+<TYPE>INFORMATION TABLE
+<TEXT>
+<XML>
+<ns1:informationTable xmlns:ns1="http://www.sec.gov/edgar/document/thirteenf/informationtable">
+  <ns1:infoTable>
+    <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+    <ns1:titleOfClass>COM</ns1:titleOfClass>
+    <ns1:cusip>037833100</ns1:cusip>
+    <ns1:value>1234</ns1:value>
+    <ns1:shrsOrPrnAmt>
+      <ns1:sshPrnamt>100</ns1:sshPrnamt>
+      <ns1:sshPrnamtType>SH</ns1:sshPrnamtType>
+    </ns1:shrsOrPrnAmt>
+    <ns1:putCall>Put</ns1:putCall>
+    <ns1:investmentDiscretion>SOLE</ns1:investmentDiscretion>
+    <ns1:votingAuthority>
+      <ns1:Sole>100</ns1:Sole>
+      <ns1:Shared>0</ns1:Shared>
+      <ns1:None>0</ns1:None>
+    </ns1:votingAuthority>
+  </ns1:infoTable>
+</ns1:informationTable>
+</XML>
+</TEXT>
+</DOCUMENT>
+</SUBMISSION>
+"#;
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        let holdings = parsed.information_table().unwrap();
+        assert_eq!(holdings.len(), 1);
+        assert_eq!(holdings[0].name_of_issuer.as_deref(), Some("APPLE INC"));
+        assert_eq!(holdings[0].cusip.as_deref(), Some("037833100"));
+        assert_eq!(holdings[0].value, Some(1234.0));
+        assert_eq!(holdings[0].shares_or_principal, Some(100.0));
+        assert_eq!(holdings[0].put_call.as_deref(), Some("PUT"));
+        assert_eq!(holdings[0].voting_sole, Some(100.0));
+    }
+
+    #[test]
+    fn test_text_information_table() {
+        let table = b"\
+                                   FORM 13F INFORMATION TABLE
+                                                    VALUE    SHARES/  SH/ PUT/ INVSTMT  OTHER       VOTING AUTHORITY
+NAME OF ISSUER                 TITLE OF CLASS   CUSIP     (x$1000) PRN AMT  PRN CALL DSCRETN  MANAGERS  SOLE   SHARED   NONE
+------------------------------ ---------------- --------- -------- -------- --- ---- -------- -------- ------ -------- ----
+<S>                            <C>              <C>       <C>      <C>      <C> <C>  <C>      <C>      <C>    <C>      <C>
+APPLE COMPUTER INC             COM              037833100    1,234   10,000 SH       SOLE                10,000      0    0
+INTERNATIONAL BUSINESS MACHS   COM              459200101      500    5,000 SH  CALL DEFINED  1 2         4,000  1,000    0
+";
+        let holdings = parse_text_information_table(table);
+        assert_eq!(holdings.len(), 2);
+
+        assert_eq!(holdings[0].name_of_issuer.as_deref(), Some("APPLE COMPUTER INC"));
+        assert_eq!(holdings[0].title_of_class.as_deref(), Some("COM"));
+        assert_eq!(holdings[0].value, Some(1234.0));
+        assert_eq!(holdings[0].shares_or_principal, Some(10000.0));
+        assert_eq!(holdings[0].investment_discretion.as_deref(), Some("SOLE"));
+        assert_eq!(holdings[0].voting_sole, Some(10000.0));
+
+        assert_eq!(holdings[1].cusip.as_deref(), Some("459200101"));
+        assert_eq!(holdings[1].put_call.as_deref(), Some("CALL"));
+        assert_eq!(holdings[1].other_managers.as_deref(), Some("1 2"));
+        assert_eq!(holdings[1].voting_shared, Some(1000.0));
+    }
+
+    #[test]
+    fn test_is_cusip() {
+        assert!(is_cusip("037833100"));
+        assert!(is_cusip("459200101"));
+        assert!(!is_cusip("037833101"));
+        assert!(!is_cusip("(x$1000)"));
+    }
+}
@@ -1,5 +1,6 @@
 //! Header field name mappings for standardization
 
+use crate::types::MetadataValue;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 use std::borrow::Cow;
@@ -55,6 +56,8 @@ pub fn get_header_mappings() -> &'static HashMap<&'static str, HeaderMapping> {
         m.insert("zip", HeaderMapping::simple("zip"));
         m.insert("business phone", HeaderMapping::simple("phone"));
         m.insert("mail address", HeaderMapping::simple("mail-address"));
+        m.insert("mail-address", HeaderMapping::simple("mail-address"));
+        m.insert("business-address", HeaderMapping::simple("business-address"));
         m.insert("former company", HeaderMapping::simple("former-company"));
         m.insert("former conformed name", HeaderMapping::simple("former-conformed-name"));
         m.insert("date of name change", HeaderMapping::simple("date-changed"));
@@ -63,6 +66,18 @@ pub fn get_header_mappings() -> &'static HashMap<&'static str, HeaderMapping> {
         m.insert("standard industrial classification", HeaderMapping::with_regex("assigned-sic", r"\[(\d+)\]"));
         m.insert("irs number", HeaderMapping::simple("irs-number"));
         m.insert("filed by", HeaderMapping::simple("filed-by"));
+        m.insert("filed-by", HeaderMapping::simple("filed-by"));
+        m.insert("subject-company", HeaderMapping::simple("subject-company"));
+        m.insert("reporting owner", HeaderMapping::simple("reporting-owner"));
+        m.insert("reporting-owner", HeaderMapping::simple("reporting-owner"));
+        m.insert("issuer", HeaderMapping::simple("issuer"));
+        m.insert("owner data", HeaderMapping::simple("owner-data"));
+        m.insert("owner-data", HeaderMapping::simple("owner-data"));
+        m.insert("company-data", HeaderMapping::simple("company-data"));
+        m.insert("former name", HeaderMapping::simple("former-name"));
+        m.insert("former-name", HeaderMapping::simple("former-name"));
+        m.insert("former-company", HeaderMapping::simple("former-company"));
+        m.insert("group-members", HeaderMapping::simple("group-members"));
         m.insert("items", HeaderMapping::simple("items"));
         m.insert("group members", HeaderMapping::simple("group-members"));
         m.insert("organization name", HeaderMapping::simple("organization-name"));
@@ -89,6 +104,8 @@ pub fn get_header_mappings() -> &'static HashMap<&'static str, HeaderMapping> {
         m.insert("target-data", HeaderMapping::simple("target-data"));
         m.insert("new-classes-contracts", HeaderMapping::simple("new-classes-contracts"));
         m.insert("new-series", HeaderMapping::simple("new-series"));
+        m.insert("class-contract", HeaderMapping::simple("class-contract"));
+        m.insert("merger", HeaderMapping::simple("merger"));
         m.insert("relationship", HeaderMapping::simple("relationship"));
 
         m
@@ -125,6 +142,17 @@ pub fn standardize_key(key: &str) -> Cow<'static, str> {
     
     Cow::Owned(result)
 }
+/// Build the key of a field derived from `key`, matching its spelling style
+pub fn derived_key(key: &str, suffix: &str) -> String {
+    if key.bytes().any(|b| b.is_ascii_lowercase()) {
+        format!("{}-{}", key, suffix)
+    } else if key.contains(' ') {
+        format!("{} {}", key, suffix.to_ascii_uppercase().replace('-', " "))
+    } else {
+        format!("{}-{}", key, suffix.to_ascii_uppercase())
+    }
+}
+
 /// Apply regex transformation if the key has one defined
 pub fn transform_value(key: &str, value: &str) -> String {
     let key_lower = key.to_lowercase();
@@ -162,6 +190,45 @@ pub fn transform_value(key: &str, value: &str) -> String {
     value.to_string()
 }
 
+/// Sections that can occur more than once in a header
+pub const REPEATABLE_SECTIONS: &[&str] = &[
+    "filer",
+    "subject-company",
+    "filed-by",
+    "reporting-owner",
+    "issuer",
+    "former-company",
+    "former-name",
+    "series",
+    "new-series",
+    "class-contract",
+    "merger",
+];
+
+/// Wrap single occurrences of repeatable sections in a list, at any depth,
+/// so consumers see the same shape for one filer as for many.
+pub fn normalize_repeatable_sections(fields: &mut HashMap<String, MetadataValue>) {
+    for (key, value) in fields.iter_mut() {
+        match value {
+            MetadataValue::Object(obj) => {
+                normalize_repeatable_sections(obj);
+                if REPEATABLE_SECTIONS.contains(&standardize_key(key).as_ref()) {
+                    let single = std::mem::take(value);
+                    *value = MetadataValue::List(vec![single]);
+                }
+            }
+            MetadataValue::List(items) => {
+                for item in items {
+                    if let MetadataValue::Object(obj) = item {
+                        normalize_repeatable_sections(obj);
+                    }
+                }
+            }
+            MetadataValue::String(_) => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +259,24 @@ mod tests {
         let result = transform_value("COMPANY CONFORMED NAME", "ACME CORP");
         assert_eq!(result, "ACME CORP");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_normalize_repeatable_sections() {
+        let mut former = HashMap::new();
+        former.insert("former-conformed-name".to_string(), MetadataValue::string("OLD CORP"));
+        let mut filer = HashMap::new();
+        filer.insert("former-company".to_string(), MetadataValue::Object(former));
+        let mut fields = HashMap::new();
+        fields.insert("FILER".to_string(), MetadataValue::Object(filer));
+        fields.insert("type".to_string(), MetadataValue::string("8-K"));
+
+        normalize_repeatable_sections(&mut fields);
+
+        let MetadataValue::List(filers) = &fields["FILER"] else {
+            panic!("filer should be a list");
+        };
+        let filer = filers[0].as_object().unwrap();
+        assert!(matches!(&filer["former-company"], MetadataValue::List(l) if l.len() == 1));
+        assert_eq!(fields["type"].as_str(), Some("8-K"));
+    }
+}
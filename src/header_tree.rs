@@ -0,0 +1,176 @@
+//! Header tree construction
+//!
+//! Tab headers (`COMPANY DATA:` with indentation) and Archive headers
+//! (`<COMPANY-DATA>` tags) describe the same tree. Each format is read into
+//! the same `HeaderEvent`s, and `HeaderBuilder` turns those into the tree,
+//! applying key standardization and value transforms in one place so both
+//! formats produce the same metadata.
+//!
+//! Open sections are kept on a stack of owned maps and folded into their
+//! parent when they close, so a value always has somewhere to go.
+
+use crate::header_mappings::{standardize_key, transform_value};
+use crate::types::MetadataValue;
+use std::collections::HashMap;
+
+/// One step of a header, with keys and values as written in the filing
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HeaderEvent<'a> {
+    /// A section starts; following fields belong to it
+    Open(&'a str),
+    /// The named section ends
+    Close(&'a str),
+    /// A field and its (possibly empty) value
+    Field(&'a str, &'a str),
+}
+
+/// Builds a nested header map from header events
+#[derive(Debug, Default)]
+pub(crate) struct HeaderBuilder {
+    standardize: bool,
+    root: HashMap<String, MetadataValue>,
+    /// Open sections, innermost last
+    stack: Vec<(String, HashMap<String, MetadataValue>)>,
+    diagnostics: Vec<String>,
+}
+
+impl HeaderBuilder {
+    pub fn new(standardize: bool) -> Self {
+        Self {
+            standardize,
+            root: HashMap::with_capacity(128),
+            ..Default::default()
+        }
+    }
+
+    pub fn apply(&mut self, event: HeaderEvent<'_>) {
+        match event {
+            HeaderEvent::Open(key) => {
+                let key = self.key(key);
+                self.stack.push((key, HashMap::new()));
+            }
+            HeaderEvent::Close(key) => {
+                let key = self.key(key);
+                self.close(&key);
+            }
+            HeaderEvent::Field(key, value) => {
+                let value = if self.standardize {
+                    transform_value(key, value)
+                } else {
+                    value.to_string()
+                };
+                let key = self.key(key);
+                self.insert(key, MetadataValue::String(value));
+            }
+        }
+    }
+
+    /// Record a problem found while reading the header
+    pub fn diagnostic(&mut self, message: String) {
+        self.diagnostics.push(message);
+    }
+
+    /// Close any open sections and return the tree with its diagnostics
+    pub fn finish(mut self) -> (HashMap<String, MetadataValue>, Vec<String>) {
+        self.close_to(0);
+        (self.root, self.diagnostics)
+    }
+
+    fn key(&self, key: &str) -> String {
+        if self.standardize {
+            standardize_key(key).into_owned()
+        } else {
+            key.to_string()
+        }
+    }
+
+    /// Add a value to the innermost open section
+    fn insert(&mut self, key: String, value: MetadataValue) {
+        let target = match self.stack.last_mut() {
+            Some((_, map)) => map,
+            None => &mut self.root,
+        };
+        insert_or_append(target, key, value);
+    }
+
+    /// Close the named section and any sections still open inside it.
+    ///
+    /// A close with no matching open section is reported and ignored.
+    fn close(&mut self, key: &str) {
+        match self.stack.iter().rposition(|(open, _)| open == key) {
+            Some(pos) => {
+                for (inner, _) in &self.stack[pos + 1..] {
+                    self.diagnostics
+                        .push(format!("section {:?} closed implicitly by {:?}", inner, key));
+                }
+                self.close_to(pos);
+            }
+            None => self
+                .diagnostics
+                .push(format!("closing tag for {:?} has no open section", key)),
+        }
+    }
+
+    /// Close sections until at most `depth` remain open
+    fn close_to(&mut self, depth: usize) {
+        while self.stack.len() > depth {
+            if let Some((key, map)) = self.stack.pop() {
+                self.insert(key, MetadataValue::Object(map));
+            }
+        }
+    }
+}
+
+/// Insert value into map, converting to list if key exists
+fn insert_or_append(map: &mut HashMap<String, MetadataValue>, key: String, value: MetadataValue) {
+    if let Some(existing) = map.get_mut(&key) {
+        match existing {
+            MetadataValue::List(list) => {
+                list.push(value);
+            }
+            _ => {
+                let old = std::mem::replace(existing, MetadataValue::List(Vec::new()));
+                if let MetadataValue::List(list) = existing {
+                    list.push(old);
+                    list.push(value);
+                }
+            }
+        }
+    } else {
+        map.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_folds_sections() {
+        let mut builder = HeaderBuilder::new(true);
+        for event in [
+            HeaderEvent::Field("CONFORMED SUBMISSION TYPE", "SC 13D"),
+            HeaderEvent::Open("FILER"),
+            HeaderEvent::Open("COMPANY DATA"),
+            HeaderEvent::Field("CENTRAL INDEX KEY", "1"),
+            HeaderEvent::Close("FILER"),
+            HeaderEvent::Open("FILER"),
+            HeaderEvent::Close("SUBJECT COMPANY"),
+        ] {
+            builder.apply(event);
+        }
+
+        let (root, diagnostics) = builder.finish();
+        assert_eq!(root["type"].as_str(), Some("SC 13D"));
+        let filers = match &root["filer"] {
+            MetadataValue::List(filers) => filers,
+            other => panic!("expected list, got {:?}", other),
+        };
+        assert_eq!(filers.len(), 2);
+        assert_eq!(
+            filers[0].as_object().unwrap()["company-data"].as_object().unwrap()["cik"].as_str(),
+            Some("1")
+        );
+        assert_eq!(diagnostics.len(), 2);
+    }
+}
@@ -0,0 +1,287 @@
+//! Typed EDGAR identifiers
+//!
+//! Header values such as `cik` and `accession-number` come out of the parser
+//! as raw strings with inconsistent padding and dashes. These newtypes
+//! validate and normalize them, display in canonical EDGAR form, and
+//! (de)serialize as that canonical string.
+
+use crate::error::ParseError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Central Index Key, displayed zero-padded to 10 digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cik(u64);
+
+impl Cik {
+    pub fn new(value: u64) -> Self {
+        Cik(value)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for Cik {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.len() > 10 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("CIK", s));
+        }
+        Ok(Cik(s.parse().map_err(|_| invalid("CIK", s))?))
+    }
+}
+
+impl fmt::Display for Cik {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:010}", self.0)
+    }
+}
+
+/// Accession number (`0000774352-01-000002`)
+///
+/// The first part is the CIK of the filer agent that submitted the filing,
+/// which is not necessarily the company the filing is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccessionNumber {
+    filer_agent: Cik,
+    year: u8,
+    sequence: u32,
+}
+
+impl AccessionNumber {
+    /// CIK of the entity that submitted the filing
+    pub fn filer_agent_cik(&self) -> Cik {
+        self.filer_agent
+    }
+
+    /// Four-digit year the accession number was assigned
+    pub fn year(&self) -> u16 {
+        // EDGAR started in 1993; two-digit years from 80 on are 19xx
+        if self.year >= 80 {
+            1900 + self.year as u16
+        } else {
+            2000 + self.year as u16
+        }
+    }
+
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /// Undashed 18-digit form used in archive folder names
+    pub fn compact(&self) -> String {
+        format!("{:010}{:02}{:06}", self.filer_agent.0, self.year, self.sequence)
+    }
+
+    /// Archive folder path for this filing under a company CIK
+    /// (`edgar/data/774352/000077435201000002`)
+    pub fn archive_path(&self, cik: Cik) -> String {
+        format!("edgar/data/{}/{}", cik.value(), self.compact())
+    }
+
+    /// URL of the full submission text file
+    pub fn archive_url(&self, cik: Cik) -> String {
+        format!("https://www.sec.gov/Archives/{}/{}.txt", self.archive_path(cik), self)
+    }
+}
+
+impl FromStr for AccessionNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let digits: String = match s.len() {
+            20 if s.as_bytes()[10] == b'-' && s.as_bytes()[13] == b'-' => {
+                s.chars().filter(|&c| c != '-').collect()
+            }
+            18 => s.to_string(),
+            _ => return Err(invalid("accession number", s)),
+        };
+
+        if digits.len() != 18 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("accession number", s));
+        }
+
+        let parse = |range: std::ops::Range<usize>| -> Result<u64, ParseError> {
+            digits[range].parse().map_err(|_| invalid("accession number", s))
+        };
+        Ok(AccessionNumber {
+            filer_agent: Cik(parse(0..10)?),
+            year: parse(10..12)? as u8,
+            sequence: parse(12..18)? as u32,
+        })
+    }
+}
+
+impl fmt::Display for AccessionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:010}-{:02}-{:06}", self.filer_agent.0, self.year, self.sequence)
+    }
+}
+
+/// SEC file number (`333-57481`, `812-15801-228`)
+///
+/// Numeric prefixes are zero-padded to three digits, so `0-19470`
+/// normalizes to `000-19470`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileNumber(String);
+
+impl FileNumber {
+    /// Registration type prefix (`333`, `000`, `812`, ...)
+    pub fn prefix(&self) -> &str {
+        self.0.split('-').next().unwrap_or("")
+    }
+
+    /// Everything after the prefix
+    pub fn number(&self) -> &str {
+        self.0.split_once('-').map(|(_, rest)| rest).unwrap_or("")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for FileNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cleaned: String = s
+            .trim()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        let (prefix, rest) = cleaned
+            .split_once('-')
+            .ok_or_else(|| invalid("file number", s))?;
+
+        let is_valid = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric());
+        if !is_valid(prefix) || !rest.split('-').all(is_valid) {
+            return Err(invalid("file number", s));
+        }
+
+        let prefix = if prefix.len() < 3 && prefix.bytes().all(|b| b.is_ascii_digit()) {
+            format!("{:0>3}", prefix)
+        } else {
+            prefix.to_string()
+        };
+
+        Ok(FileNumber(format!("{}-{}", prefix, rest)))
+    }
+}
+
+impl fmt::Display for FileNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Film number assigned at dissemination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FilmNumber(u64);
+
+impl FilmNumber {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for FilmNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("film number", s));
+        }
+        Ok(FilmNumber(s.parse().map_err(|_| invalid("film number", s))?))
+    }
+}
+
+impl fmt::Display for FilmNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn invalid(kind: &str, value: &str) -> ParseError {
+    ParseError::InvalidIdentifier(format!("{} {:?}", kind, value))
+}
+
+/// Serialize as the canonical string and deserialize through `FromStr`
+macro_rules! impl_string_serde {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let s = String::deserialize(deserializer)?;
+                    s.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_string_serde!(Cik, AccessionNumber, FileNumber, FilmNumber);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cik() {
+        let cik: Cik = "774352".parse().unwrap();
+        assert_eq!(cik.to_string(), "0000774352");
+        assert_eq!(cik, "0000774352".parse().unwrap());
+        assert!("77435x".parse::<Cik>().is_err());
+        assert!("12345678901".parse::<Cik>().is_err());
+    }
+
+    #[test]
+    fn test_accession_number() {
+        let dashed: AccessionNumber = "0000774352-01-000002".parse().unwrap();
+        let compact: AccessionNumber = "000077435201000002".parse().unwrap();
+        assert_eq!(dashed, compact);
+        assert_eq!(compact.to_string(), "0000774352-01-000002");
+        assert_eq!(dashed.compact(), "000077435201000002");
+        assert_eq!(dashed.filer_agent_cik(), Cik::new(774352));
+        assert_eq!(dashed.year(), 2001);
+        assert_eq!("0000950123-94-000001".parse::<AccessionNumber>().unwrap().year(), 1994);
+        assert_eq!(
+            dashed.archive_url(Cik::new(774352)),
+            "https://www.sec.gov/Archives/edgar/data/774352/000077435201000002/0000774352-01-000002.txt"
+        );
+        assert!("0000774352-01-00002".parse::<AccessionNumber>().is_err());
+    }
+
+    #[test]
+    fn test_file_number() {
+        assert_eq!("0-19470".parse::<FileNumber>().unwrap().to_string(), "000-19470");
+        let file_number: FileNumber = "812-15801-228".parse().unwrap();
+        assert_eq!(file_number.prefix(), "812");
+        assert_eq!(file_number.number(), "15801-228");
+        assert!("33357481".parse::<FileNumber>().is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let accession: AccessionNumber = "000077435201000002".parse().unwrap();
+        let json = serde_json::to_string(&accession).unwrap();
+        assert_eq!(json, "\"0000774352-01-000002\"");
+        assert_eq!(serde_json::from_str::<AccessionNumber>(&json).unwrap(), accession);
+        assert!(serde_json::from_str::<Cik>("\"abc\"").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! EDGAR full-index and daily-index file parsing
+//!
+//! Handles the fixed-width `form.idx`, `company.idx` and `crawler.idx`
+//! layouts and the pipe-delimited `master.idx` layout. Records use the
+//! same key names and value formats as standardized submission metadata
+//! (`cik` zero-padded to 10 digits, `filing-date` as `YYYYMMDD`,
+//! `accession-number` dashed) so they can be joined directly.
+
+use crate::compression::decompress;
+use crate::error::{ParseError, Result};
+use crate::ids::{AccessionNumber, Cik};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Layout of an index file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexKind {
+    /// Fixed width, sorted by form type
+    Form,
+    /// Fixed width, sorted by company name
+    Company,
+    /// Pipe delimited, sorted by CIK
+    Master,
+    /// Fixed width with filing index URLs
+    Crawler,
+}
+
+/// A single filing listed in an index file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexRecord {
+    pub cik: Cik,
+    pub conformed_name: String,
+    pub form_type: String,
+    pub filing_date: String,
+    /// Archive path (`edgar/data/...`) or, for crawler indexes, the filing index URL
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accession_number: Option<AccessionNumber>,
+}
+
+/// A row that is not a record, with its zero-based line number
+pub type RejectedRow = (usize, String);
+
+/// Parse an index file from a path, decompressing it if needed
+pub fn parse_index_file(path: impl AsRef<Path>) -> Result<(IndexKind, Vec<IndexRecord>, Vec<RejectedRow>)> {
+    let data = std::fs::read(path)?;
+    parse_index(&decompress(&data)?)
+}
+
+/// Parse an index file, detecting its layout from the column header.
+///
+/// Rows that cannot be read as a record (too few fields, a CIK that is not
+/// a number) are returned alongside the records.
+pub fn parse_index(data: &[u8]) -> Result<(IndexKind, Vec<IndexRecord>, Vec<RejectedRow>)> {
+    let text = String::from_utf8_lossy(data);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let header_pos = lines
+        .iter()
+        .position(|line| is_column_header(line))
+        .ok_or_else(|| ParseError::InvalidStructure("Missing index column header".into()))?;
+    let header = lines[header_pos];
+
+    // Data starts after the dashed separator line
+    let rows = lines
+        .iter()
+        .enumerate()
+        .skip(header_pos + 1)
+        .skip_while(|(_, line)| !line.starts_with("---"))
+        .skip(1)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_number, line)| (line_number, *line));
+
+    if header.contains('|') {
+        let (records, rejected) = read_rows(rows, parse_master_row);
+        return Ok((IndexKind::Master, records, rejected));
+    }
+
+    let columns = column_starts(header);
+    let kind = if header.trim_start().starts_with("Form Type") {
+        IndexKind::Form
+    } else if header.trim_end().ends_with("URL") {
+        IndexKind::Crawler
+    } else {
+        IndexKind::Company
+    };
+
+    let (records, rejected) = read_rows(rows, |line| parse_fixed_row(line, &columns, kind));
+    Ok((kind, records, rejected))
+}
+
+/// Parse each row, setting aside those that are not records
+fn read_rows<'a>(
+    rows: impl Iterator<Item = (usize, &'a str)>,
+    parse_row: impl Fn(&str) -> Option<IndexRecord>,
+) -> (Vec<IndexRecord>, Vec<RejectedRow>) {
+    let mut records = Vec::new();
+    let mut rejected = Vec::new();
+    for (line_number, line) in rows {
+        match parse_row(line) {
+            Some(record) => records.push(record),
+            None => rejected.push((line_number, line.to_string())),
+        }
+    }
+    (records, rejected)
+}
+
+fn is_column_header(line: &str) -> bool {
+    line.starts_with("CIK|") || (line.contains("Form Type") && line.contains("Date Filed"))
+}
+
+/// Byte offsets of each column name in a fixed-width header line
+fn column_starts(header: &str) -> Vec<usize> {
+    const NAMES: &[&str] = &["Form Type", "Company Name", "CIK", "Date Filed", "File Name", "URL"];
+    let mut starts: Vec<usize> = NAMES.iter().filter_map(|name| header.find(name)).collect();
+    starts.sort_unstable();
+    starts
+}
+
+fn parse_fixed_row(line: &str, columns: &[usize], kind: IndexKind) -> Option<IndexRecord> {
+    if columns.len() != 5 {
+        return None;
+    }
+
+    // Long company names can overflow into the CIK column, so take the
+    // last three fields as whitespace-separated tokens from the right.
+    let mut rest = line.trim_end();
+    let mut take_last = || -> Option<&str> {
+        let start = rest.rfind(char::is_whitespace)? + 1;
+        let token = &rest[start..];
+        rest = rest[..start].trim_end();
+        Some(token)
+    };
+    let path = take_last()?;
+    let date = take_last()?;
+    let cik = take_last()?;
+
+    // The first two columns are split at the header offset, moved past a
+    // multibyte character that starts before it
+    let mut split = columns[1].min(rest.len());
+    while !rest.is_char_boundary(split) {
+        split += 1;
+    }
+    let (first, second) = rest.split_at(split);
+    let (first, second) = (first.trim(), second.trim());
+
+    let (form_type, conformed_name) = match kind {
+        IndexKind::Form => (first, second),
+        _ => (second, first),
+    };
+
+    Some(IndexRecord {
+        cik: cik.parse().ok()?,
+        conformed_name: conformed_name.to_string(),
+        form_type: form_type.to_string(),
+        filing_date: normalize_date(date),
+        path: path.to_string(),
+        accession_number: accession_from_path(path),
+    })
+}
+
+fn parse_master_row(line: &str) -> Option<IndexRecord> {
+    let mut parts = line.split('|');
+    let cik = parts.next()?;
+    let conformed_name = parts.next()?;
+    let form_type = parts.next()?;
+    let date = parts.next()?;
+    let path = parts.next()?.trim();
+
+    Some(IndexRecord {
+        cik: cik.parse().ok()?,
+        conformed_name: conformed_name.trim().to_string(),
+        form_type: form_type.trim().to_string(),
+        filing_date: normalize_date(date.trim()),
+        path: path.to_string(),
+        accession_number: accession_from_path(path),
+    })
+}
+
+/// Convert `YYYY-MM-DD` to the `YYYYMMDD` form used in headers
+fn normalize_date(date: &str) -> String {
+    date.replace('-', "")
+}
+
+/// Extract the accession number from an archive path or index URL
+fn accession_from_path(path: &str) -> Option<AccessionNumber> {
+    let name = path.rsplit('/').next()?;
+    name.get(..20)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORM_IDX: &str = "\
+Description:           Daily Index of EDGAR Dissemination Feed by Form Type
+Last Data Received:    January 2, 2024
+Comments:              webmaster@sec.gov
+Anonymous FTP:         ftp://ftp.sec.gov/edgar/
+
+Form Type   Company Name                                                  CIK         Date Filed  File Name
+---------------------------------------------------------------------------------------------------------------------------------------------
+10-K        ACME CORP                                                     774352      20240102    edgar/data/774352/0000774352-24-000002.txt
+8-K         WIDGETS INTERNATIONAL HOLDINGS INCORPORATED OF AMERICA AND CO 1201001     20240102    edgar/data/1201001/0001193125-24-000001.txt
+";
+
+    const MASTER_IDX: &str = "\
+Description:           Master Index of EDGAR Dissemination Feed
+Comments:              webmaster@sec.gov
+
+CIK|Company Name|Form Type|Date Filed|Filename
+--------------------------------------------------------------------------------
+774352|ACME CORP|10-K|2024-01-02|edgar/data/774352/0000774352-24-000002.txt
+";
+
+    const CRAWLER_IDX: &str = "\
+Description:           Daily Index of EDGAR Dissemination Feed by Company Name
+
+Company Name                                                  Form Type   CIK         Date Filed  URL
+-------------------------------------------------------------------------------------------------------------------------------------------------------------
+ACME CORP                                                     10-K        774352      2024-01-02  https://www.sec.gov/Archives/edgar/data/774352/0000774352-24-000002-index.htm
+";
+
+    #[test]
+    fn test_parse_form_idx() {
+        let (kind, records, rejected) = parse_index(FORM_IDX.as_bytes()).unwrap();
+        assert_eq!(kind, IndexKind::Form);
+        assert_eq!(records.len(), 2);
+        assert!(rejected.is_empty());
+        assert_eq!(records[0].cik.to_string(), "0000774352");
+        assert_eq!(records[0].form_type, "10-K");
+        assert_eq!(records[0].conformed_name, "ACME CORP");
+        assert_eq!(records[0].filing_date, "20240102");
+        assert_eq!(records[0].accession_number.unwrap().to_string(), "0000774352-24-000002");
+        assert_eq!(records[1].cik, Cik::new(1201001));
+    }
+
+    #[test]
+    fn test_multibyte_name_across_column() {
+        let header = format!("{:<62}{:<12}{:<12}{:<12}File Name", "Company Name", "Form Type", "CIK", "Date Filed");
+        // `É` takes bytes 61 and 62, across the `Form Type` offset
+        let row = format!("{}É10-K        774352      2024-01-02  edgar/data/774352/0000774352-24-000002.txt", "A".repeat(61));
+        let idx = format!("{}\n{}\n{}\n", header, "-".repeat(120), row);
+
+        let (kind, records, rejected) = parse_index(idx.as_bytes()).unwrap();
+        assert_eq!(kind, IndexKind::Company);
+        assert_eq!(records.len(), 1);
+        assert!(rejected.is_empty());
+        assert_eq!(records[0].conformed_name, format!("{}É", "A".repeat(61)));
+        assert_eq!(records[0].form_type, "10-K");
+    }
+
+    #[test]
+    fn test_parse_master_idx() {
+        let (kind, records, rejected) = parse_index(MASTER_IDX.as_bytes()).unwrap();
+        assert_eq!(kind, IndexKind::Master);
+        assert!(rejected.is_empty());
+        assert_eq!(records[0].cik.to_string(), "0000774352");
+        assert_eq!(records[0].filing_date, "20240102");
+        assert_eq!(records[0].path, "edgar/data/774352/0000774352-24-000002.txt");
+    }
+
+    #[test]
+    fn test_parse_crawler_idx() {
+        let (kind, records, rejected) = parse_index(CRAWLER_IDX.as_bytes()).unwrap();
+        assert_eq!(kind, IndexKind::Crawler);
+        assert!(rejected.is_empty());
+        assert_eq!(records[0].form_type, "10-K");
+        assert_eq!(records[0].accession_number.unwrap().to_string(), "0000774352-24-000002");
+    }
+
+    #[test]
+    fn test_malformed_rows_returned() {
+        let idx = format!(
+            "{}ACME CORP|10-K|2024-01-02\nWIDGETS|8-K|2024-01-02|edgar/data/x.txt\n",
+            MASTER_IDX
+        );
+        let (_, records, rejected) = parse_index(idx.as_bytes()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            rejected,
+            vec![
+                (6, "ACME CORP|10-K|2024-01-02".to_string()),
+                (7, "WIDGETS|8-K|2024-01-02|edgar/data/x.txt".to_string()),
+            ]
+        );
+
+        let idx = format!("{}truncated row\n", FORM_IDX);
+        let (_, records, rejected) = parse_index(idx.as_bytes()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(rejected, vec![(9, "truncated row".to_string())]);
+    }
+}
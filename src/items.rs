@@ -0,0 +1,368 @@
+//! Item segmentation of 10-K, 10-Q and 8-K text
+//!
+//! Periodic and current reports are organized into numbered items
+//! (`Item 1A. Risk Factors`, `Item 2.02 Results of Operations...`). Item
+//! headings are found at the start of lines of the rendered text. Most
+//! reports open with a table of contents repeating every heading, so when
+//! a heading occurs more than once the later occurrence is taken.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An item of a report and its text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Item {
+    /// `I` or `II` for 10-Q items, which restart numbering in each part
+    pub part: Option<String>,
+    /// `1A`, `7`, `2.02`, ...
+    pub id: String,
+    /// Heading text after the item number, as written
+    pub title: Option<String>,
+    /// Byte offset of the heading in the segmented text
+    pub start: usize,
+    /// Byte offset where the next item begins
+    pub end: usize,
+    pub text: String,
+}
+
+/// Items of Form 8-K since August 2004
+pub const FORM_8K_ITEMS: &[(&str, &str)] = &[
+    ("1.01", "Entry into a Material Definitive Agreement"),
+    ("1.02", "Termination of a Material Definitive Agreement"),
+    ("1.03", "Bankruptcy or Receivership"),
+    ("1.04", "Mine Safety - Reporting of Shutdowns and Patterns of Violations"),
+    ("1.05", "Material Cybersecurity Incidents"),
+    ("2.01", "Completion of Acquisition or Disposition of Assets"),
+    ("2.02", "Results of Operations and Financial Condition"),
+    ("2.03", "Creation of a Direct Financial Obligation or an Obligation under an Off-Balance Sheet Arrangement of a Registrant"),
+    ("2.04", "Triggering Events That Accelerate or Increase a Direct Financial Obligation or an Obligation under an Off-Balance Sheet Arrangement"),
+    ("2.05", "Costs Associated with Exit or Disposal Activities"),
+    ("2.06", "Material Impairments"),
+    ("3.01", "Notice of Delisting or Failure to Satisfy a Continued Listing Rule or Standard; Transfer of Listing"),
+    ("3.02", "Unregistered Sales of Equity Securities"),
+    ("3.03", "Material Modification to Rights of Security Holders"),
+    ("4.01", "Changes in Registrant's Certifying Accountant"),
+    ("4.02", "Non-Reliance on Previously Issued Financial Statements or a Related Audit Report or Completed Interim Review"),
+    ("5.01", "Changes in Control of Registrant"),
+    ("5.02", "Departure of Directors or Certain Officers; Election of Directors; Appointment of Certain Officers; Compensatory Arrangements of Certain Officers"),
+    ("5.03", "Amendments to Articles of Incorporation or Bylaws; Change in Fiscal Year"),
+    ("5.04", "Temporary Suspension of Trading Under Registrant's Employee Benefit Plans"),
+    ("5.05", "Amendments to the Registrant's Code of Ethics, or Waiver of a Provision of the Code of Ethics"),
+    ("5.06", "Change in Shell Company Status"),
+    ("5.07", "Submission of Matters to a Vote of Security Holders"),
+    ("5.08", "Shareholder Director Nominations"),
+    ("6.01", "ABS Informational and Computational Material"),
+    ("6.02", "Change of Servicer or Trustee"),
+    ("6.03", "Change in Credit Enhancement or Other External Support"),
+    ("6.04", "Failure to Make a Required Distribution"),
+    ("6.05", "Securities Act Updating Disclosure"),
+    ("6.06", "Static Pool"),
+    ("7.01", "Regulation FD Disclosure"),
+    ("8.01", "Other Events"),
+    ("9.01", "Financial Statements and Exhibits"),
+];
+
+const FORM_10K_ITEMS: &[&str] = &[
+    "1", "1A", "1B", "1C", "2", "3", "4", "4A", "5", "6", "7", "7A", "8", "9", "9A", "9B", "9C", "10", "11",
+    "12", "13", "14", "15", "16",
+];
+
+const FORM_10Q_ITEMS: &[&str] = &["1", "1A", "2", "3", "4", "5", "6"];
+
+/// Item number of a Form 8-K item description, as tab headers list them
+/// under `ITEM INFORMATION`
+pub fn form_8k_item_number(description: &str) -> Option<&'static str> {
+    let description = description.trim().trim_end_matches('.');
+    FORM_8K_ITEMS
+        .iter()
+        .find(|(_, title)| title.eq_ignore_ascii_case(description))
+        .map(|(id, _)| *id)
+}
+
+/// Split the text of a 10-K, 10-Q or 8-K into items.
+///
+/// `form` is the document or submission type; amendments and variants
+/// (`10-K405`, `10-Q/A`) are segmented like the base form. Other forms
+/// have no items. A line starting with `Item <number>` is a heading only
+/// when the number is followed by the end of the line, punctuation
+/// (`Item 7.`, `Item 7 -`) or a capitalized title, so cross-references
+/// wrapped onto a new line (`Item 7, Management's ...`, `Item 9.01 of this
+/// report`) are not. When a heading repeats, the last one wins, which
+/// skips the table of contents. When `only` is not empty, headings of
+/// other items are ignored.
+pub fn segment_items(text: &str, form: &str, only: &[&str]) -> Vec<Item> {
+    let form = form.trim().to_ascii_uppercase();
+    let kind = if form.starts_with("10-K") {
+        Form::Annual
+    } else if form.starts_with("10-Q") {
+        Form::Quarterly
+    } else if form.starts_with("8-K") {
+        Form::Current
+    } else {
+        return Vec::new();
+    };
+
+    // Last heading for each (part, id)
+    let mut headings: HashMap<(Option<String>, String), (usize, Option<String>)> = HashMap::new();
+    let mut part = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        let start = start + (line.len() - trimmed.len());
+        if kind == Form::Quarterly {
+            if let Some(numeral) = part_heading(trimmed) {
+                part = Some(numeral);
+                continue;
+            }
+        }
+
+        let Some((id, title)) = item_heading(trimmed.trim_end(), kind) else {
+            continue;
+        };
+        if !only.is_empty() && !only.contains(&id.as_str()) {
+            continue;
+        }
+        headings.insert((part.clone(), id), (start, title));
+    }
+
+    let mut items: Vec<Item> = headings
+        .into_iter()
+        .map(|((part, id), (start, title))| Item {
+            part,
+            id,
+            title,
+            start,
+            ..Default::default()
+        })
+        .collect();
+    items.sort_by_key(|item| item.start);
+
+    let starts: Vec<usize> = items.iter().map(|item| item.start).skip(1).collect();
+    for (item, next) in items.iter_mut().zip(starts.into_iter().map(Some).chain([None])) {
+        let end = next.unwrap_or(text.len());
+        item.end = item.start + text[item.start..end].trim_end().len();
+        item.text = text[item.start..item.end].to_string();
+    }
+
+    items
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Form {
+    Annual,
+    Quarterly,
+    Current,
+}
+
+/// `PART I`, `PART II`, ... starting a line
+fn part_heading(line: &str) -> Option<String> {
+    let rest = strip_prefix_ignore_case(line, "part")?;
+    let rest = rest.trim_start();
+    let numeral: String = rest.chars().take_while(|c| matches!(c, 'I' | 'V' | 'i' | 'v')).collect();
+    let after = &rest[numeral.len()..];
+    if numeral.is_empty() || after.starts_with(|c: char| c.is_alphanumeric()) {
+        return None;
+    }
+    Some(numeral.to_ascii_uppercase())
+}
+
+/// `Item 1A. Risk Factors` into the item id and title
+fn item_heading(line: &str, kind: Form) -> Option<(String, Option<String>)> {
+    let rest = strip_prefix_ignore_case(line, "item")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 || digits > 2 {
+        return None;
+    }
+    let mut id_len = digits;
+    let bytes = rest.as_bytes();
+    if kind == Form::Current
+        && bytes.get(digits) == Some(&b'.')
+        && bytes.get(digits + 1..digits + 3).is_some_and(|d| d.iter().all(u8::is_ascii_digit))
+    {
+        id_len = digits + 3;
+    } else if kind != Form::Current
+        && bytes.get(digits).is_some_and(|b| matches!(b.to_ascii_uppercase(), b'A'..=b'C'))
+        && !bytes.get(digits + 1).is_some_and(u8::is_ascii_alphanumeric)
+    {
+        id_len = digits + 1;
+    }
+    if bytes.get(id_len).is_some_and(u8::is_ascii_alphanumeric) {
+        return None;
+    }
+
+    // A heading ends after the number or goes on with punctuation or a
+    // title; a cross-reference goes on with `,`, `of`, `and`, ...
+    let after = &rest[id_len..];
+    let next = after.trim_start().chars().next();
+    let heading_shape = match next {
+        None => true,
+        Some('.' | ':' | '-' | '–' | '—' | '|') => true,
+        Some(c) => after.starts_with(char::is_whitespace) && c.is_uppercase(),
+    };
+    if !heading_shape {
+        return None;
+    }
+
+    let id = rest[..id_len].to_ascii_uppercase();
+    let known = match kind {
+        Form::Annual => FORM_10K_ITEMS.contains(&id.as_str()),
+        Form::Quarterly => FORM_10Q_ITEMS.contains(&id.as_str()),
+        // Before August 2004, 8-K items were numbered 1 to 12
+        Form::Current => {
+            FORM_8K_ITEMS.iter().any(|(known, _)| *known == id)
+                || id.parse::<u8>().is_ok_and(|n| (1..=12).contains(&n))
+        }
+    };
+    if !known {
+        return None;
+    }
+
+    // Table of contents rows render as `Item 1. | Business | 3`
+    let title = rest[id_len..]
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '.' | ':' | '-' | '–' | '—' | '|'))
+        .split(" | ")
+        .next()
+        .unwrap_or_default()
+        .trim();
+    Some((id, (!title.is_empty()).then(|| title.to_string())))
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &s[prefix.len()..])
+}
+
+#[cfg(feature = "text")]
+impl crate::types::ParsedSubmission {
+    /// Items of the primary document of a 10-K, 10-Q or 8-K, rendered as
+    /// text. 8-K headings are limited to the items the header lists.
+    pub fn items(&self) -> Vec<Item> {
+        let Some(form) = self.metadata.submission_type() else {
+            return Vec::new();
+        };
+        let listed = self.metadata.items();
+        let only: Vec<&str> = listed.iter().map(String::as_str).collect();
+        let only = if form.to_ascii_uppercase().starts_with("8-K") { &only[..] } else { &[] };
+
+        self.primary_document()
+            .and_then(|(meta, content)| crate::text::document_to_text(meta, content))
+            .map(|text| segment_items(&text, form, only))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEN_K: &str = "ANNUAL REPORT
+
+TABLE OF CONTENTS
+Item 1. | Business | 3
+Item 1A. | Risk Factors | 10
+Item 7. | Management's Discussion and Analysis | 20
+
+PART I
+
+Item 1. Business
+
+We make widgets.
+
+ITEM 1A.  RISK FACTORS
+
+Widgets may fail. See Item 7.
+
+Item 7 - Management's Discussion and Analysis
+
+Sales grew.
+";
+
+    #[test]
+    fn test_ten_k_items_skip_table_of_contents() {
+        let items = segment_items(TEN_K, "10-K405", &[]);
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "1A", "7"]);
+
+        assert_eq!(items[0].title.as_deref(), Some("Business"));
+        assert_eq!(items[0].text, "Item 1. Business\n\nWe make widgets.");
+        assert_eq!(&TEN_K[items[0].start..items[0].end], items[0].text);
+        assert_eq!(items[1].title.as_deref(), Some("RISK FACTORS"));
+        assert_eq!(items[2].title.as_deref(), Some("Management's Discussion and Analysis"));
+        assert!(items[2].text.ends_with("Sales grew."));
+    }
+
+    #[test]
+    fn test_wrapped_cross_reference() {
+        let text = "Item 7. Management's Discussion and Analysis\n\
+Sales grew.\n\
+Item 8. Financial Statements\n\
+For segment results see\n\
+Item 7, Management's Discussion and Analysis, and\n\
+Item 7 of Part II.\n";
+        let items = segment_items(text, "10-K", &[]);
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["7", "8"]);
+        assert_eq!(items[0].text, "Item 7. Management's Discussion and Analysis\nSales grew.");
+        assert!(items[1].text.ends_with("Item 7 of Part II."));
+    }
+
+    #[test]
+    fn test_ten_q_parts() {
+        let text = "PART I\nItem 1. Financial Statements\nTables\nPART II\nItem 1. Legal Proceedings\nNone\nItem 1A. Risk Factors\nNo changes\n";
+        let items = segment_items(text, "10-Q", &[]);
+        let keys: Vec<(Option<&str>, &str)> =
+            items.iter().map(|item| (item.part.as_deref(), item.id.as_str())).collect();
+        assert_eq!(keys, vec![(Some("I"), "1"), (Some("II"), "1"), (Some("II"), "1A")]);
+    }
+
+    #[test]
+    fn test_eight_k_items() {
+        let text = "Item 2.02 Results of Operations and Financial Condition.\n\
+On May 1 the Company reported results, furnished as Exhibit 99.1 under\n\
+Item 9.01 of this report.\n\
+Item 9.01 Financial Statements and Exhibits.\n\
+(d) Exhibits\n";
+        // The cross-reference is not a heading even without `only`
+        let items = segment_items(text, "8-K", &[]);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "2.02");
+        assert!(items[0].text.ends_with("Item 9.01 of this report."));
+        assert_eq!(items[1].title.as_deref(), Some("Financial Statements and Exhibits."));
+        assert_eq!(items[1].text, "Item 9.01 Financial Statements and Exhibits.\n(d) Exhibits");
+
+        let only_results = segment_items(text, "8-K", &["2.02"]);
+        assert_eq!(only_results.len(), 1);
+        assert!(only_results[0].text.ends_with("(d) Exhibits"));
+
+        assert_eq!(form_8k_item_number("Results of Operations and Financial Condition"), Some("2.02"));
+        assert!(segment_items(text, "S-1", &[]).is_empty());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn test_submission_items() {
+        use crate::{parse_sgml, ParseOptions};
+
+        let data = b"<SUBMISSION>\n<TYPE>8-K\n<ITEMS>2.02\n<ITEMS>9.01\n\
+<DOCUMENT>\n<TYPE>8-K\n<SEQUENCE>1\n<FILENAME>form8-k.htm\n<TEXT>\n\
+<html><body><p><b>Item&#160;2.02</b> Results of Operations and Financial Condition</p>\
+<p>See Item 8.01 below.</p><p>Item 9.01 Financial Statements and Exhibits</p></body></html>\n\
+</TEXT>\n</DOCUMENT>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert_eq!(parsed.metadata.items(), vec!["2.02", "9.01"]);
+
+        let items = parsed.items();
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["2.02", "9.01"]);
+        assert_eq!(items[0].title.as_deref(), Some("Results of Operations and Financial Condition"));
+    }
+}
@@ -0,0 +1,285 @@
+//! Legacy SGML tables in text documents
+//!
+//! Before HTML, EDGAR text filings marked tables with `<TABLE>` ...
+//! `</TABLE>`, an optional `<CAPTION>` holding the column headings and a
+//! line of `<S>` and `<C>` markers whose positions give the start of the
+//! stub column and of each data column. Rows below the marker line are
+//! fixed-width text cut at those positions.
+
+use crate::types::{DocumentMetadata, ParsedSubmission};
+use serde::{Deserialize, Serialize};
+
+/// A table found in a text document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LegacyTable {
+    /// Caption lines as written, markup removed
+    pub caption: Vec<String>,
+    /// Column headings assembled from the caption, one per column
+    pub headers: Vec<String>,
+    /// Trimmed cells of each data row; rules (`----`, `====`), blank
+    /// lines and the `<PAGE>` breaks of tables spanning pages are left out
+    pub rows: Vec<Vec<String>>,
+    /// Zero-based line of the `<TABLE>` tag in the document
+    pub line: usize,
+}
+
+/// Find the `<TABLE>` blocks of a text document.
+///
+/// Tables without an `<S>`/`<C>` marker line have a single column. Blocks
+/// containing HTML rows are skipped.
+pub fn parse_legacy_tables(text: &[u8]) -> Vec<LegacyTable> {
+    let text = String::from_utf8_lossy(text);
+    let lines: Vec<&str> = text.lines().collect();
+    let mut tables = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        if !lines[i].trim().eq_ignore_ascii_case("<TABLE>") {
+            i += 1;
+            continue;
+        }
+        let end = lines[i + 1..]
+            .iter()
+            .position(|line| line.trim().eq_ignore_ascii_case("</TABLE>"))
+            .map_or(lines.len(), |pos| i + 1 + pos);
+        let body = &lines[i + 1..end];
+        if !body.iter().any(|line| is_html_row(line)) {
+            tables.push(read_table(body, i));
+        }
+        i = end + 1;
+    }
+
+    tables
+}
+
+impl ParsedSubmission {
+    /// Legacy tables of every kept text document that has any
+    pub fn legacy_tables(&self) -> Vec<(&DocumentMetadata, Vec<LegacyTable>)> {
+        self.documents_with_metadata()
+            .filter(|(meta, _)| {
+                !meta.filename().is_some_and(|name| {
+                    let name = name.to_ascii_lowercase();
+                    name.ends_with(".htm") || name.ends_with(".html")
+                })
+            })
+            .map(|(meta, content)| (meta, parse_legacy_tables(content)))
+            .filter(|(_, tables)| !tables.is_empty())
+            .collect()
+    }
+}
+
+fn read_table(body: &[&str], line: usize) -> LegacyTable {
+    let markers = body.iter().position(|line| is_marker_line(line));
+    let columns = markers.map(|pos| column_starts(body[pos])).unwrap_or_else(|| vec![0]);
+
+    let mut caption = Vec::new();
+    let mut in_caption = false;
+    // Set once the first caption block ends; later ones are page repeats
+    let mut captions_done = false;
+    let mut rows = Vec::new();
+    for (i, raw) in body.iter().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.eq_ignore_ascii_case("<CAPTION>") {
+            in_caption = true;
+            continue;
+        }
+        if trimmed.eq_ignore_ascii_case("</CAPTION>") {
+            in_caption = false;
+            captions_done = true;
+            continue;
+        }
+        // Tables broken across pages repeat the marker line, and sometimes
+        // the caption, after each `<PAGE>`
+        if is_marker_line(raw) {
+            in_caption = false;
+            captions_done = true;
+            continue;
+        }
+        if trimmed.to_ascii_uppercase().starts_with("<PAGE>") {
+            continue;
+        }
+
+        // Anything above the first marker line is caption, whether tagged
+        // or not, as is the first tagged caption of a table without markers;
+        // repeated captions further down are dropped
+        let above_markers = markers.is_some_and(|pos| i < pos);
+        if in_caption || above_markers {
+            if (above_markers || !captions_done) && !trimmed.is_empty() {
+                caption.push(raw.trim_end().to_string());
+            }
+        } else if !is_rule(trimmed) {
+            rows.push(split_columns(raw, &columns));
+        }
+    }
+
+    let mut headers = vec![String::new(); columns.len()];
+    for line in &caption {
+        for (header, cell) in headers.iter_mut().zip(split_columns(line, &columns)) {
+            if !cell.is_empty() && !is_rule(&cell) {
+                if !header.is_empty() {
+                    header.push(' ');
+                }
+                header.push_str(&cell);
+            }
+        }
+    }
+
+    LegacyTable {
+        caption,
+        headers,
+        rows,
+        line,
+    }
+}
+
+/// A line made only of `<S>` and `<C>` markers
+fn is_marker_line(line: &str) -> bool {
+    let upper = line.to_ascii_uppercase();
+    let rest = upper.replace("<S>", "").replace("<C>", "");
+    rest.trim().is_empty() && upper.len() != rest.len()
+}
+
+fn is_html_row(line: &str) -> bool {
+    let upper = line.to_ascii_uppercase();
+    upper.contains("<TR") || upper.contains("<TD")
+}
+
+/// Underlines and blank lines, which carry no cells
+fn is_rule(line: &str) -> bool {
+    line.chars().all(|c| matches!(c, '-' | '=' | '_' | ' '))
+}
+
+/// Character offsets of the markers on a marker line
+fn column_starts(line: &str) -> Vec<usize> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut starts: Vec<usize> = (0..chars.len().saturating_sub(2))
+        .filter(|&i| {
+            chars[i] == '<'
+                && matches!(chars[i + 1].to_ascii_uppercase(), 'S' | 'C')
+                && chars[i + 2] == '>'
+        })
+        .collect();
+    // Stub text can start left of the `<S>` marker
+    if let Some(first) = starts.first_mut() {
+        *first = 0;
+    }
+    starts
+}
+
+/// Cut a row at the column starts. A token crossing a boundary belongs to
+/// the column on the right, since numbers are right-aligned and tend to
+/// spill left of their marker.
+fn split_columns(line: &str, starts: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut bounds: Vec<usize> = starts
+        .iter()
+        .map(|&start| {
+            let mut start = start.min(chars.len());
+            while start > 0 && start < chars.len() && !chars[start].is_whitespace() && !chars[start - 1].is_whitespace() {
+                start -= 1;
+            }
+            start
+        })
+        .collect();
+    bounds.push(chars.len());
+
+    bounds
+        .windows(2)
+        .map(|pair| {
+            let (start, end) = (pair[0], pair[1].max(pair[0]));
+            chars[start..end].iter().collect::<String>().trim().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEN_K: &[u8] = b"ITEM 6. SELECTED FINANCIAL DATA
+
+<TABLE>
+<CAPTION>
+                                     FISCAL        FISCAL
+                                      1996          1995
+                                   ----------    ----------
+<S>                                <C>           <C>
+Net sales.......................   $1,234,567    $1,100,000
+Net income (loss)...............     (12,345)       45,678
+                                   ==========    ==========
+</TABLE>
+
+<TABLE>
+<TR><TD>html</TD></TR>
+</TABLE>
+";
+
+    #[test]
+    fn test_legacy_table() {
+        let tables = parse_legacy_tables(TEN_K);
+        assert_eq!(tables.len(), 1);
+
+        let table = &tables[0];
+        assert_eq!(table.line, 2);
+        assert_eq!(table.caption.len(), 3);
+        assert_eq!(table.headers, vec!["", "FISCAL 1996", "FISCAL 1995"]);
+        assert_eq!(
+            table.rows,
+            vec![
+                vec!["Net sales.......................", "$1,234,567", "$1,100,000"],
+                vec!["Net income (loss)...............", "(12,345)", "45,678"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_across_pages() {
+        let text = b"<TABLE>
+<CAPTION>
+                                      1996          1995
+<S>                                <C>           <C>
+Net sales.......................   $1,234,567    $1,100,000
+</TABLE>
+";
+        let broken = b"<TABLE>
+<CAPTION>
+                                      1996          1995
+<S>                                <C>           <C>
+Net sales.......................   $1,234,567    $1,100,000
+<PAGE>   12
+<CAPTION>
+                                      1996          1995
+</CAPTION>
+<S>                                <C>           <C>
+Net income......................       12,345        45,678
+</TABLE>
+";
+        let table = &parse_legacy_tables(broken)[0];
+        assert_eq!(table.caption, parse_legacy_tables(text)[0].caption);
+        assert_eq!(table.headers, vec!["", "1996", "1995"]);
+        assert_eq!(
+            table.rows,
+            vec![
+                vec!["Net sales.......................", "$1,234,567", "$1,100,000"],
+                vec!["Net income......................", "12,345", "45,678"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_without_markers() {
+        let tables = parse_legacy_tables(b"<TABLE>\nfirst row\n\nsecond row\n</TABLE>\n");
+        assert_eq!(tables[0].rows, vec![vec!["first row"], vec!["second row"]]);
+        assert!(tables[0].caption.is_empty());
+    }
+
+    #[test]
+    fn test_captioned_table_without_markers() {
+        let text = b"<TABLE>\n<CAPTION>\nSelected data\n</CAPTION>\nRow one   1\n<PAGE>\n<CAPTION>\nSelected data\n</CAPTION>\nRow two   2\n</TABLE>\n";
+        let table = &parse_legacy_tables(text)[0];
+        assert_eq!(table.caption, vec!["Selected data"]);
+        assert_eq!(table.headers, vec!["Selected data"]);
+        assert_eq!(table.rows, vec![vec!["Row one   1"], vec!["Row two   2"]]);
+    }
+}
@@ -2,10 +2,26 @@
 //!
 //! High-performance parser for SEC SGML filings.
 
+mod codes;
+mod compression;
+mod dates;
+mod encoding;
+mod entity;
 mod error;
+mod exhibits;
+mod filter;
+pub mod financial_data;
 mod header_mappings;
+mod header_tree;
+mod ids;
+pub mod index;
+pub mod items;
+pub mod legacy_table;
+mod pem;
+mod series;
 mod types;
 pub mod uudecode;
+mod visitor;
 mod parse;
 
 
@@ -13,13 +29,61 @@ mod parse;
 #[cfg(feature = "python")]
 mod python;
 
+#[cfg(feature = "feed")]
+pub mod feed;
+
+#[cfg(any(feature = "ownership", feature = "form13f", feature = "xbrl"))]
+mod xml;
+
+#[cfg(feature = "ownership")]
+pub mod ownership;
+
+#[cfg(feature = "form13f")]
+pub mod form13f;
+
+#[cfg(feature = "xbrl")]
+pub mod xbrl;
+
+#[cfg(feature = "text")]
+pub mod text;
+
+pub use codes::{sic_code, state_name, SicCode};
+pub use compression::{decompress, decompressing_reader, Compression};
+pub use dates::{MonthDay, SecDate, SecDateTime};
+pub use encoding::{decode_text, Encoding};
+pub use entity::{Address, Entity, FilingValues, FormerName};
 pub use error::{ParseError, Result};
+pub use exhibits::ExhibitKind;
+pub use filter::{ContentType, DocumentFilter};
+pub use financial_data::{parse_financial_data_schedule, FinancialDataSchedule, PeriodType, ScheduleColumn};
+pub use ids::{AccessionNumber, Cik, FileNumber, FilmNumber};
+pub use index::{parse_index, parse_index_file, IndexKind, IndexRecord, RejectedRow};
+pub use items::{segment_items, Item};
+pub use legacy_table::{parse_legacy_tables, LegacyTable};
 pub use types::{
     DocumentMetadata, MetadataValue, ParseOptions, ParsedSubmission, 
     SubmissionFormat, SubmissionMetadata,
 };
-pub use parse::{parse_sgml, parse_sgml_file};
+pub use parse::{parse_sgml, parse_sgml_file, visit_sgml, visit_sgml_file};
+pub use pem::{parse_privacy_enhanced_message, PrivacyEnhancedMessage};
+pub use series::{ClassContract, Merger, Series, SeriesAndClasses};
 pub use uudecode::decode_uuencoded;
+pub use visitor::SubmissionVisitor;
+
+#[cfg(feature = "feed")]
+pub use feed::{FeedEntry, FeedReader};
+
+#[cfg(feature = "ownership")]
+pub use ownership::{parse_ownership_document, OwnershipDocument};
+
+#[cfg(feature = "form13f")]
+pub use form13f::{parse_information_table, parse_text_information_table};
+
+#[cfg(feature = "xbrl")]
+pub use xbrl::{parse_xbrl, Fact, Period};
+
+#[cfg(feature = "text")]
+pub use text::{document_to_text, html_to_text, normalize_legacy_text};
 
 
 /// Parse SGML and return JSON metadata bytes + document contents.
@@ -36,6 +100,7 @@ pub fn parse_sgml_into_memory(
         filter_document_types,
         keep_filtered_metadata,
         standardize_metadata,
+        ..Default::default()
     };
     
     let result = parse_sgml(data, options)?;
@@ -0,0 +1,430 @@
+//! Form 3, 4 and 5 ownership document parsing
+//!
+//! Electronic ownership filings carry an `ownershipDocument` XML payload
+//! describing the issuer, the reporting owners and their non-derivative
+//! and derivative transactions and holdings. Values that the XML wraps in
+//! `<value>` elements are unwrapped, and footnote references are kept per
+//! row so they can be joined to `footnotes`.
+
+use crate::error::{ParseError, Result};
+use crate::ids::Cik;
+use crate::types::ParsedSubmission;
+use crate::xml::{child, children, flag, number, parse_xml, text_at, xml_text};
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+
+/// Document types that carry an ownership document
+pub const OWNERSHIP_FORMS: &[&str] = &["3", "4", "5", "3/A", "4/A", "5/A"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OwnershipDocument {
+    pub schema_version: Option<String>,
+    /// `3`, `4`, `5`, `3/A`, ...
+    pub document_type: Option<String>,
+    pub period_of_report: Option<String>,
+    pub not_subject_to_section16: bool,
+    pub issuer: Issuer,
+    pub reporting_owners: Vec<ReportingOwner>,
+    pub non_derivative_transactions: Vec<Transaction>,
+    pub non_derivative_holdings: Vec<Holding>,
+    pub derivative_transactions: Vec<Transaction>,
+    pub derivative_holdings: Vec<Holding>,
+    pub footnotes: Vec<Footnote>,
+    pub remarks: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Issuer {
+    pub cik: Option<Cik>,
+    pub name: Option<String>,
+    pub trading_symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReportingOwner {
+    pub cik: Option<Cik>,
+    pub name: Option<String>,
+    pub street1: Option<String>,
+    pub street2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub is_director: bool,
+    pub is_officer: bool,
+    pub is_ten_percent_owner: bool,
+    pub is_other: bool,
+    pub officer_title: Option<String>,
+    pub other_text: Option<String>,
+}
+
+/// A row of the non-derivative or derivative transaction table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Transaction {
+    pub security_title: Option<String>,
+    pub transaction_date: Option<String>,
+    pub deemed_execution_date: Option<String>,
+    pub form_type: Option<String>,
+    /// Transaction code (`P`, `S`, `A`, `M`, ...)
+    pub code: Option<String>,
+    pub equity_swap_involved: bool,
+    pub shares: Option<f64>,
+    pub price_per_share: Option<f64>,
+    /// `A` (acquired) or `D` (disposed)
+    pub acquired_disposed: Option<String>,
+    pub shares_owned_following: Option<f64>,
+    /// `D` (direct) or `I` (indirect)
+    pub direct_or_indirect: Option<String>,
+    pub nature_of_ownership: Option<String>,
+    /// Derivative rows only
+    pub conversion_or_exercise_price: Option<f64>,
+    pub exercise_date: Option<String>,
+    pub expiration_date: Option<String>,
+    pub underlying_security_title: Option<String>,
+    pub underlying_shares: Option<f64>,
+    /// Ids of the footnotes referenced anywhere in the row
+    pub footnote_ids: Vec<String>,
+}
+
+/// A row of the non-derivative or derivative holding table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Holding {
+    pub security_title: Option<String>,
+    pub shares_owned: Option<f64>,
+    pub direct_or_indirect: Option<String>,
+    pub nature_of_ownership: Option<String>,
+    /// Derivative rows only
+    pub conversion_or_exercise_price: Option<f64>,
+    pub exercise_date: Option<String>,
+    pub expiration_date: Option<String>,
+    pub underlying_security_title: Option<String>,
+    pub underlying_shares: Option<f64>,
+    pub footnote_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Footnote {
+    pub id: String,
+    pub text: String,
+}
+
+/// Parse an `ownershipDocument` XML payload
+pub fn parse_ownership_document(data: &[u8]) -> Result<OwnershipDocument> {
+    let text = xml_text(data);
+    let xml = parse_xml(&text)?;
+    let root = xml.root_element();
+    if root.tag_name().name() != "ownershipDocument" {
+        return Err(ParseError::XmlError(format!(
+            "expected ownershipDocument, found {}",
+            root.tag_name().name()
+        )));
+    }
+
+    let issuer = child(root, "issuer")
+        .map(|issuer| Issuer {
+            cik: text_at(issuer, &["issuerCik"]).and_then(|s| s.parse().ok()),
+            name: text_at(issuer, &["issuerName"]),
+            trading_symbol: text_at(issuer, &["issuerTradingSymbol"]),
+        })
+        .unwrap_or_default();
+
+    Ok(OwnershipDocument {
+        schema_version: text_at(root, &["schemaVersion"]),
+        document_type: text_at(root, &["documentType"]),
+        period_of_report: text_at(root, &["periodOfReport"]),
+        not_subject_to_section16: text_at(root, &["notSubjectToSection16"]).is_some_and(|s| flag(&s)),
+        issuer,
+        reporting_owners: children(root, "reportingOwner").map(reporting_owner).collect(),
+        non_derivative_transactions: rows(root, "nonDerivativeTable", "nonDerivativeTransaction")
+            .into_iter()
+            .map(transaction)
+            .collect(),
+        non_derivative_holdings: rows(root, "nonDerivativeTable", "nonDerivativeHolding")
+            .into_iter()
+            .map(holding)
+            .collect(),
+        derivative_transactions: rows(root, "derivativeTable", "derivativeTransaction")
+            .into_iter()
+            .map(transaction)
+            .collect(),
+        derivative_holdings: rows(root, "derivativeTable", "derivativeHolding")
+            .into_iter()
+            .map(holding)
+            .collect(),
+        footnotes: child(root, "footnotes")
+            .map(|footnotes| {
+                children(footnotes, "footnote")
+                    .map(|note| Footnote {
+                        id: note.attribute("id").unwrap_or_default().to_string(),
+                        text: text_at(note, &[]).unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        remarks: text_at(root, &["remarks"]),
+    })
+}
+
+impl ParsedSubmission {
+    /// Parse the ownership documents of a Form 3, 4 or 5 submission.
+    ///
+    /// Documents of those types without an `ownershipDocument`, such as
+    /// the text forms filed before 2003, are skipped.
+    pub fn ownership_documents(&self) -> Result<Vec<OwnershipDocument>> {
+        self.documents_of_type(OWNERSHIP_FORMS)
+            .filter(|(_, content)| memchr::memmem::find(content, b"<ownershipDocument").is_some())
+            .map(|(_, content)| parse_ownership_document(content))
+            .collect()
+    }
+}
+
+fn reporting_owner(node: Node) -> ReportingOwner {
+    let flag_at = |path: &[&str]| text_at(node, path).is_some_and(|s| flag(&s));
+    let address = |name: &str| text_at(node, &["reportingOwnerAddress", name]);
+    let relationship = |name: &'static str| ["reportingOwnerRelationship", name];
+
+    ReportingOwner {
+        cik: text_at(node, &["reportingOwnerId", "rptOwnerCik"]).and_then(|s| s.parse().ok()),
+        name: text_at(node, &["reportingOwnerId", "rptOwnerName"]),
+        street1: address("rptOwnerStreet1"),
+        street2: address("rptOwnerStreet2"),
+        city: address("rptOwnerCity"),
+        state: address("rptOwnerState"),
+        zip: address("rptOwnerZipCode"),
+        is_director: flag_at(&relationship("isDirector")),
+        is_officer: flag_at(&relationship("isOfficer")),
+        is_ten_percent_owner: flag_at(&relationship("isTenPercentOwner")),
+        is_other: flag_at(&relationship("isOther")),
+        officer_title: text_at(node, &relationship("officerTitle")),
+        other_text: text_at(node, &relationship("otherText")),
+    }
+}
+
+fn transaction(node: Node) -> Transaction {
+    let holding = holding(node);
+    Transaction {
+        security_title: holding.security_title,
+        transaction_date: value(node, &["transactionDate"]),
+        deemed_execution_date: value(node, &["deemedExecutionDate"]),
+        form_type: text_at(node, &["transactionCoding", "transactionFormType"]),
+        code: text_at(node, &["transactionCoding", "transactionCode"]),
+        equity_swap_involved: text_at(node, &["transactionCoding", "equitySwapInvolved"])
+            .is_some_and(|s| flag(&s)),
+        shares: value(node, &["transactionAmounts", "transactionShares"]).and_then(|s| number(&s)),
+        price_per_share: value(node, &["transactionAmounts", "transactionPricePerShare"])
+            .and_then(|s| number(&s)),
+        acquired_disposed: value(node, &["transactionAmounts", "transactionAcquiredDisposedCode"]),
+        shares_owned_following: holding.shares_owned,
+        direct_or_indirect: holding.direct_or_indirect,
+        nature_of_ownership: holding.nature_of_ownership,
+        conversion_or_exercise_price: holding.conversion_or_exercise_price,
+        exercise_date: holding.exercise_date,
+        expiration_date: holding.expiration_date,
+        underlying_security_title: holding.underlying_security_title,
+        underlying_shares: holding.underlying_shares,
+        footnote_ids: holding.footnote_ids,
+    }
+}
+
+/// Fields shared by holding and transaction rows
+fn holding(node: Node) -> Holding {
+    // Each id once, in the order first referenced
+    let mut footnote_ids: Vec<String> = Vec::new();
+    for id in node
+        .descendants()
+        .filter(|n| n.tag_name().name() == "footnoteId")
+        .filter_map(|n| n.attribute("id"))
+    {
+        if !footnote_ids.iter().any(|seen| seen == id) {
+            footnote_ids.push(id.to_string());
+        }
+    }
+
+    Holding {
+        security_title: value(node, &["securityTitle"]),
+        shares_owned: value(node, &["postTransactionAmounts", "sharesOwnedFollowingTransaction"])
+            .and_then(|s| number(&s)),
+        direct_or_indirect: value(node, &["ownershipNature", "directOrIndirectOwnership"]),
+        nature_of_ownership: value(node, &["ownershipNature", "natureOfOwnership"]),
+        conversion_or_exercise_price: value(node, &["conversionOrExercisePrice"]).and_then(|s| number(&s)),
+        exercise_date: value(node, &["exerciseDate"]),
+        expiration_date: value(node, &["expirationDate"]),
+        underlying_security_title: value(node, &["underlyingSecurity", "underlyingSecurityTitle"]),
+        underlying_shares: value(node, &["underlyingSecurity", "underlyingSecurityShares"])
+            .and_then(|s| number(&s)),
+        footnote_ids,
+    }
+}
+
+/// Row elements of a transaction or holding table
+fn rows<'a, 'input>(root: Node<'a, 'input>, table: &str, row: &str) -> Vec<Node<'a, 'input>> {
+    child(root, table)
+        .map(|table| children(table, row).collect())
+        .unwrap_or_default()
+}
+
+/// Text of the `<value>` element under `path`
+fn value(node: Node, path: &[&str]) -> Option<String> {
+    let mut node = node;
+    for name in path {
+        node = child(node, name)?;
+    }
+    text_at(node, &["value"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    const FORM_4: &str = r#"<SUBMISSION>
+<TYPE>4
+<DOCUMENT>
+<TYPE>4
+<SEQUENCE>1
+<FILENAME>form4.xml
+<TEXT>
+<XML>
+<?xml version="1.0"?>
+<ownershipDocument>
+    <schemaVersion>X0306</schemaVersion>
+    <documentType>4</documentType>
+    <periodOfReport>2024-01-02</periodOfReport>
+    <issuer>
+        <issuerCik>0000012345</issuerCik>
+        <issuerName>WIDGETS INC</issuerName>
+        <issuerTradingSymbol>WDGT</issuerTradingSymbol>
+    </issuer>
+    <reportingOwner>
+        <reportingOwnerId>
+            <rptOwnerCik>0001234567</rptOwnerCik>
+            <rptOwnerName>DOE JOHN</rptOwnerName>
+        </reportingOwnerId>
+        <reportingOwnerAddress>
+            <rptOwnerCity>NEW YORK</rptOwnerCity>
+        </reportingOwnerAddress>
+        <reportingOwnerRelationship>
+            <isDirector>1</isDirector>
+            <isOfficer>true</isOfficer>
+            <officerTitle>CEO</officerTitle>
+        </reportingOwnerRelationship>
+    </reportingOwner>
+    <nonDerivativeTable>
+        <nonDerivativeTransaction>
+            <securityTitle><value>Common Stock</value></securityTitle>
+            <transactionDate><value>2024-01-02</value><footnoteId id="F2"/></transactionDate>
+            <transactionCoding>
+                <transactionFormType>4</transactionFormType>
+                <transactionCode>S</transactionCode>
+                <equitySwapInvolved>0</equitySwapInvolved>
+            </transactionCoding>
+            <transactionAmounts>
+                <transactionShares><value>1,000</value></transactionShares>
+                <transactionPricePerShare><value>10.25</value><footnoteId id="F1"/></transactionPricePerShare>
+                <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+            </transactionAmounts>
+            <postTransactionAmounts>
+                <sharesOwnedFollowingTransaction><value>9000</value><footnoteId id="F2"/></sharesOwnedFollowingTransaction>
+            </postTransactionAmounts>
+            <ownershipNature>
+                <directOrIndirectOwnership><value>D</value></directOrIndirectOwnership>
+            </ownershipNature>
+        </nonDerivativeTransaction>
+        <nonDerivativeHolding>
+            <securityTitle><value>Common Stock</value></securityTitle>
+            <postTransactionAmounts>
+                <sharesOwnedFollowingTransaction><value>500</value></sharesOwnedFollowingTransaction>
+            </postTransactionAmounts>
+            <ownershipNature>
+                <directOrIndirectOwnership><value>I</value></directOrIndirectOwnership>
+                <natureOfOwnership><value>By trust</value></natureOfOwnership>
+            </ownershipNature>
+        </nonDerivativeHolding>
+    </nonDerivativeTable>
+    <derivativeTable>
+        <derivativeTransaction>
+            <securityTitle><value>Stock Option</value></securityTitle>
+            <conversionOrExercisePrice><value>5.00</value></conversionOrExercisePrice>
+            <transactionDate><value>2024-01-02</value></transactionDate>
+            <transactionCoding>
+                <transactionCode>M</transactionCode>
+            </transactionCoding>
+            <transactionAmounts>
+                <transactionShares><value>1000</value></transactionShares>
+                <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+            </transactionAmounts>
+            <expirationDate><value>2030-01-02</value></expirationDate>
+            <underlyingSecurity>
+                <underlyingSecurityTitle><value>Common Stock</value></underlyingSecurityTitle>
+                <underlyingSecurityShares><value>1000</value></underlyingSecurityShares>
+            </underlyingSecurity>
+        </derivativeTransaction>
+    </derivativeTable>
+    <footnotes>
+        <footnote id="F1">Weighted average price.</footnote>
+        <footnote id="F2">Reported late.</footnote>
+    </footnotes>
+</ownershipDocument>
+</XML>
+</TEXT>
+</DOCUMENT>
+</SUBMISSION>
+"#;
+
+    #[test]
+    fn test_ownership_document() {
+        let parsed = parse_sgml(FORM_4.as_bytes(), ParseOptions::new()).unwrap();
+        let documents = parsed.ownership_documents().unwrap();
+        assert_eq!(documents.len(), 1);
+        let doc = &documents[0];
+
+        assert_eq!(doc.document_type.as_deref(), Some("4"));
+        assert_eq!(doc.issuer.cik, Some(Cik::new(12345)));
+        assert_eq!(doc.issuer.trading_symbol.as_deref(), Some("WDGT"));
+
+        let owner = &doc.reporting_owners[0];
+        assert_eq!(owner.name.as_deref(), Some("DOE JOHN"));
+        assert!(owner.is_director && owner.is_officer && !owner.is_ten_percent_owner);
+        assert_eq!(owner.officer_title.as_deref(), Some("CEO"));
+
+        let sale = &doc.non_derivative_transactions[0];
+        assert_eq!(sale.code.as_deref(), Some("S"));
+        assert_eq!(sale.shares, Some(1000.0));
+        assert_eq!(sale.price_per_share, Some(10.25));
+        assert_eq!(sale.shares_owned_following, Some(9000.0));
+        assert_eq!(sale.footnote_ids, vec!["F2", "F1"]);
+
+        let holding = &doc.non_derivative_holdings[0];
+        assert_eq!(holding.nature_of_ownership.as_deref(), Some("By trust"));
+
+        let exercise = &doc.derivative_transactions[0];
+        assert_eq!(exercise.conversion_or_exercise_price, Some(5.0));
+        assert_eq!(exercise.underlying_shares, Some(1000.0));
+        assert_eq!(exercise.expiration_date.as_deref(), Some("2030-01-02"));
+
+        assert_eq!(doc.footnotes[0].text, "Weighted average price.");
+    }
+
+    #[test]
+    fn test_text_forms_skipped() {
+        let text_form = FORM_4.replacen(
+            "<DOCUMENT>",
+            "<DOCUMENT>\n<TYPE>4\n<SEQUENCE>2\n<TEXT>\nFORM 4  STATEMENT OF CHANGES IN BENEFICIAL OWNERSHIP\n</TEXT>\n</DOCUMENT>\n<DOCUMENT>",
+            1,
+        );
+        let parsed = parse_sgml(text_form.as_bytes(), ParseOptions::new()).unwrap();
+        assert_eq!(parsed.documents_of_type(OWNERSHIP_FORMS).count(), 2);
+        assert_eq!(parsed.ownership_documents().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        assert!(parse_ownership_document(b"<informationTable/>").is_err());
+        assert!(parse_ownership_document(b"not xml").is_err());
+    }
+}
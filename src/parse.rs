@@ -1,12 +1,20 @@
 //! Core SGML parsing logic
 
+use crate::codes::enrich_codes;
+use crate::compression::decompress;
+use crate::dates::normalize_header_dates;
+use crate::encoding::Encoding;
 use crate::error::{ParseError, Result};
-use crate::header_mappings::{standardize_key, transform_value};
+use crate::header_mappings::{normalize_repeatable_sections, standardize_key, transform_value};
+use crate::header_tree::{HeaderBuilder, HeaderEvent};
+use crate::pem::{parse_privacy_enhanced_message, PrivacyEnhancedMessage};
 use crate::types::*;
 use crate::uudecode::{decode_uuencoded, is_uuencoded};
+use crate::visitor::SubmissionVisitor;
 use memchr::memmem;
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::path::Path;
 
 // Tag patterns for fast searching
@@ -16,14 +24,89 @@ const TEXT_START: &[u8] = b"<TEXT>";
 const TEXT_END: &[u8] = b"</TEXT>";
 
 /// Parse SGML from a file path using memory mapping
+///
+/// Gzip, zstd and bzip2 files are decompressed transparently when the
+/// matching feature is enabled. Only uncompressed files are parsed in
+/// place; a compressed file is decompressed into one buffer first, as the
+/// parser needs the whole submission in memory.
 pub fn parse_sgml_file(path: impl AsRef<Path>, options: ParseOptions) -> Result<ParsedSubmission> {
     let file = std::fs::File::open(path)?;
     let mmap = unsafe { memmap2::Mmap::map(&file)? };
-    parse_sgml(&mmap, options)
+    let data = decompress(&mmap)?;
+    parse_sgml(&data, options)
 }
 
 /// Parse SGML from a byte slice
 pub fn parse_sgml(data: &[u8], options: ParseOptions) -> Result<ParsedSubmission> {
+    let mut collector = Collector {
+        documents: Vec::new(),
+        doc_metas: Vec::new(),
+        keep_filtered_metadata: options.keep_filtered_metadata,
+    };
+    let walk = walk_submission(data, &options, &mut collector)?;
+
+    let mut submission_meta = walk.metadata;
+    submission_meta.documents = collector.doc_metas;
+
+    Ok(ParsedSubmission {
+        metadata: submission_meta,
+        documents: collector.documents,
+        format: walk.format,
+        privacy_enhanced_message: walk.privacy_enhanced_message,
+        diagnostics: walk.diagnostics,
+    })
+}
+
+/// Stream a submission file through `visitor`, decompressing like
+/// [`parse_sgml_file`]
+pub fn visit_sgml_file(
+    path: impl AsRef<Path>,
+    options: ParseOptions,
+    visitor: &mut impl SubmissionVisitor,
+) -> Result<()> {
+    let file = std::fs::File::open(path)?;
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    let data = decompress(&mmap)?;
+    visit_sgml(&data, options, visitor)
+}
+
+/// Stream a submission through `visitor`, one decoded document at a time
+///
+/// Documents the filters drop are skipped before decoding and never
+/// reach the visitor; `keep_filtered_metadata` has no effect here.
+pub fn visit_sgml(data: &[u8], options: ParseOptions, visitor: &mut impl SubmissionVisitor) -> Result<()> {
+    walk_submission(data, &options, &mut VisitorSink(visitor))?;
+    Ok(())
+}
+
+/// Receives the documents of a walk by value, so `parse_sgml` can keep
+/// them without copying
+trait DocumentSink {
+    fn header(&mut self, metadata: &SubmissionMetadata);
+    fn document(&mut self, metadata: DocumentMetadata, content: Vec<u8>) -> ControlFlow<()>;
+    fn filtered(&mut self, metadata: DocumentMetadata);
+}
+
+/// Everything about a submission except its documents
+struct Walk {
+    metadata: SubmissionMetadata,
+    format: SubmissionFormat,
+    privacy_enhanced_message: Option<PrivacyEnhancedMessage>,
+    diagnostics: Vec<String>,
+}
+
+/// Parse the header, then decode each document the filters keep and pass
+/// it to `sink` until the sink breaks
+fn walk_submission(data: &[u8], options: &ParseOptions, sink: &mut impl DocumentSink) -> Result<Walk> {
+    // Stop at the PEM trailer so it never ends up in a document or the
+    // header. This runs for forced formats too: the envelope is not part of
+    // any format, only `TabPrivacy` keeps its text as a header field.
+    let privacy_enhanced_message = parse_privacy_enhanced_message(data);
+    let data = match privacy_enhanced_message.as_ref().and_then(|pem| pem.end_byte) {
+        Some(end) => &data[..end],
+        None => data,
+    };
+
     // Find all document boundaries first (fast SIMD scan)
     let doc_boundaries = find_document_boundaries(data);
 
@@ -32,31 +115,77 @@ pub fn parse_sgml(data: &[u8], options: ParseOptions) -> Result<ParsedSubmission
         .first()
         .map(|(start, _)| *start)
         .unwrap_or(data.len());
-    let (mut submission_meta, format) =
-        parse_submission_metadata(&data[..header_end], options.standardize_metadata)?;
+    let (metadata, format, diagnostics) =
+        parse_submission_metadata(&data[..header_end], privacy_enhanced_message.as_ref(), options)?;
+    sink.header(&metadata);
+
+    // Parse documents sequentially, decoding only those the filters keep
+    for (start, end) in &doc_boundaries {
+        let (mut doc_meta, raw_content) =
+            split_single_document(&data[*start..*end], options.standardize_metadata)?;
+
+        if keep_document(&doc_meta, raw_content, options) {
+            let content = decode_document_content(raw_content, format);
+            doc_meta.size_bytes = content.len();
+            if options.detect_encoding && !is_uuencoded(raw_content) {
+                doc_meta.encoding = Some(Encoding::detect(&content));
+            }
+            if sink.document(doc_meta, content).is_break() {
+                break;
+            }
+        } else {
+            doc_meta.raw_size_bytes = Some(raw_content.len());
+            sink.filtered(doc_meta);
+        }
+    }
 
-    // Parse documents sequentially
-    let parsed_docs: Vec<(DocumentMetadata, Vec<u8>)> = doc_boundaries
-        .iter()
-        .map(|(start, end)| {
-            parse_single_document(&data[*start..*end], format, options.standardize_metadata)
-        })
-        .collect::<Result<Vec<_>>>()?;
+    Ok(Walk {
+        metadata,
+        format,
+        privacy_enhanced_message,
+        diagnostics,
+    })
+}
 
-    // Split metadata and content
-    let (doc_metas, documents): (Vec<_>, Vec<_>) = parsed_docs.into_iter().unzip();
+/// Sink behind `parse_sgml`: keeps every document
+struct Collector {
+    documents: Vec<Vec<u8>>,
+    doc_metas: Vec<DocumentMetadata>,
+    keep_filtered_metadata: bool,
+}
 
-    // Apply document type filter
-    let (doc_metas, documents) = apply_filter(doc_metas, documents, &options);
+impl DocumentSink for Collector {
+    fn header(&mut self, _metadata: &SubmissionMetadata) {}
 
-    submission_meta.documents = doc_metas;
+    fn document(&mut self, mut metadata: DocumentMetadata, content: Vec<u8>) -> ControlFlow<()> {
+        metadata.content_index = Some(self.documents.len());
+        self.documents.push(content);
+        self.doc_metas.push(metadata);
+        ControlFlow::Continue(())
+    }
 
-    Ok(ParsedSubmission {
-        metadata: submission_meta,
-        documents,
-        format,
-    })
+    fn filtered(&mut self, metadata: DocumentMetadata) {
+        if self.keep_filtered_metadata {
+            self.doc_metas.push(metadata);
+        }
+    }
 }
+
+/// Sink behind `visit_sgml`: lends each document to the visitor
+struct VisitorSink<'a, V>(&'a mut V);
+
+impl<V: SubmissionVisitor> DocumentSink for VisitorSink<'_, V> {
+    fn header(&mut self, metadata: &SubmissionMetadata) {
+        self.0.on_header(metadata);
+    }
+
+    fn document(&mut self, metadata: DocumentMetadata, content: Vec<u8>) -> ControlFlow<()> {
+        self.0.on_document(&metadata, &content)
+    }
+
+    fn filtered(&mut self, _metadata: DocumentMetadata) {}
+}
+
 /// Find all (start, end) byte positions of <DOCUMENT>...</DOCUMENT> blocks
 fn find_document_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
     let mut boundaries = Vec::new();
@@ -80,19 +209,16 @@ fn find_document_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
     boundaries
 }
 
-/// Parse a single <DOCUMENT>...</DOCUMENT> block
-fn parse_single_document(
-    doc_data: &[u8],
-    format: SubmissionFormat,
-    standardize: bool,
-) -> Result<(DocumentMetadata, Vec<u8>)> {
+/// Split a single <DOCUMENT>...</DOCUMENT> block into its metadata and the
+/// raw content between <TEXT> and </TEXT>
+fn split_single_document(doc_data: &[u8], standardize: bool) -> Result<(DocumentMetadata, &[u8])> {
     // Find <TEXT> tag
     let text_start = memmem::find(doc_data, TEXT_START)
         .ok_or_else(|| ParseError::InvalidStructure("Missing <TEXT> tag".into()))?;
 
     // Parse document metadata (between <DOCUMENT> and <TEXT>)
     let meta_slice = &doc_data[DOC_START.len()..text_start];
-    let mut doc_meta = parse_document_metadata(meta_slice, standardize);
+    let doc_meta = parse_document_metadata(meta_slice, standardize);
 
     // Find </TEXT> and extract content
     let content_start = text_start + TEXT_START.len();
@@ -100,19 +226,16 @@ fn parse_single_document(
         .map(|pos| content_start + pos)
         .unwrap_or(doc_data.len());
 
-    let raw_content = &doc_data[content_start..content_end];
+    Ok((doc_meta, &doc_data[content_start..content_end]))
+}
 
-    // Check if UU-encoded and decode if needed
-    let is_binary = is_uuencoded(raw_content);
-    let content = if is_binary {
+/// UU-decode binary content, clean text content
+fn decode_document_content(raw_content: &[u8], format: SubmissionFormat) -> Vec<u8> {
+    if is_uuencoded(raw_content) {
         decode_uuencoded(raw_content)
     } else {
-        clean_document_content(raw_content, format, false).to_vec()
-    };
-
-    doc_meta.size_bytes = content.len();
-
-    Ok((doc_meta, content))
+        clean_document_content(raw_content, format, false)
+    }
 }
 
 /// Parse document metadata block (key-value pairs like <TYPE>10-K)
@@ -148,8 +271,11 @@ fn parse_document_metadata(data: &[u8], standardize: bool) -> DocumentMetadata {
     DocumentMetadata {
         fields,
         size_bytes: 0,
+        raw_size_bytes: None,
         start_byte: None,
         end_byte: None,
+        encoding: None,
+        content_index: None,
     }
 }
 
@@ -171,32 +297,60 @@ fn parse_tag_line(line: &[u8]) -> Option<(&[u8], &[u8])> {
 fn detect_format(data: &[u8]) -> SubmissionFormat {
     let trimmed = trim_start(data);
 
-    if trimmed.starts_with(b"-") {
+    if trimmed.starts_with(b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----") {
         SubmissionFormat::TabPrivacy
-    } else if trimmed.starts_with(b"<SE") {
+    } else if trimmed.starts_with(b"<SEC-DOCUMENT>") {
         SubmissionFormat::TabDefault
+    } else if trimmed.starts_with(b"<SEC-HEADER>") {
+        SubmissionFormat::SecHeader
+    } else if trimmed.starts_with(b"<IMS-DOCUMENT>") || trimmed.starts_with(b"<IMS-HEADER>") {
+        SubmissionFormat::ImsDocument
+    } else if FDS_START_TAGS.iter().any(|tag| trimmed.starts_with(tag)) {
+        SubmissionFormat::FinancialDataSchedule
+    } else if trimmed.starts_with(b"<") {
+        if is_correspondence(trimmed) {
+            SubmissionFormat::Correspondence
+        } else {
+            SubmissionFormat::Archive
+        }
     } else {
-        SubmissionFormat::Archive
+        SubmissionFormat::Unknown
     }
 }
 
+/// Tags that open a bare EX-27 financial data schedule
+const FDS_START_TAGS: &[&[u8]] = &[b"<ARTICLE>", b"<LEGEND>", b"<MULTIPLIER>", b"<PERIOD-TYPE>"];
+
+/// Check whether a tagged header belongs to a CORRESP submission
+fn is_correspondence(data: &[u8]) -> bool {
+    data.split(|&b| b == b'\n')
+        .map(trim)
+        .find(|line| line.starts_with(b"<TYPE>"))
+        .map(|line| trim(&line[6..]).starts_with(b"CORRESP"))
+        .unwrap_or(false)
+}
+
 /// Parse submission header metadata
 fn parse_submission_metadata(
     data: &[u8],
-    standardize: bool,
-) -> Result<(SubmissionMetadata, SubmissionFormat)> {
-    let format = detect_format(data);
+    pem: Option<&PrivacyEnhancedMessage>,
+    options: &ParseOptions,
+) -> Result<(SubmissionMetadata, SubmissionFormat, Vec<String>)> {
+    let standardize = options.standardize_metadata;
+    let format = options.force_format.unwrap_or_else(|| detect_format(data));
 
-    let fields = match format {
+    let (mut fields, diagnostics) = match format {
         SubmissionFormat::TabPrivacy => {
-            // Find end of privacy message (first blank line)
-            let privacy_end = find_double_newline(data).unwrap_or(0);
-            let privacy_msg = bytes_to_str(&data[..privacy_end]).into_owned();
+            // The privacy message ends where the envelope parser found the
+            // body; a forced `TabPrivacy` without an envelope splits at the
+            // first blank line
+            let privacy_end = pem.map_or_else(|| find_double_newline(data).unwrap_or(0), |pem| pem.body_start_byte.min(data.len()));
+            let privacy_msg = bytes_to_str(trim_end(&data[..privacy_end])).into_owned();
 
             let rest = &data[privacy_end..];
             let rest = trim_start(rest);
 
-            let mut fields = parse_tab_metadata(rest, standardize);
+            let (mut fields, diagnostics) = parse_tab_metadata(rest, standardize);
             fields.insert(
                 if standardize {
                     "privacy-enhanced-message".into()
@@ -205,239 +359,179 @@ fn parse_submission_metadata(
                 },
                 MetadataValue::String(privacy_msg),
             );
-            fields
+            (fields, diagnostics)
         }
-        SubmissionFormat::TabDefault => parse_tab_metadata(data, standardize),
-        SubmissionFormat::Archive => parse_archive_metadata(data, standardize),
+        SubmissionFormat::TabDefault
+        | SubmissionFormat::SecHeader
+        | SubmissionFormat::ImsDocument => parse_tab_metadata(data, standardize),
+        SubmissionFormat::Archive
+        | SubmissionFormat::Correspondence
+        | SubmissionFormat::FinancialDataSchedule
+        | SubmissionFormat::Unknown => parse_archive_metadata(data, standardize),
     };
 
+    if options.normalize_dates {
+        normalize_header_dates(&mut fields);
+    }
+    if options.enrich_codes {
+        enrich_codes(&mut fields);
+    }
+    if options.normalize_lists {
+        normalize_repeatable_sections(&mut fields);
+    }
+
     Ok((
         SubmissionMetadata {
             fields,
             documents: Vec::new(),
         },
         format,
+        diagnostics,
     ))
 }
 
 /// Parse tab-delimited format metadata
-/// This format uses indentation (tabs) to indicate nesting
-fn parse_tab_metadata(data: &[u8], standardize: bool) -> HashMap<String, MetadataValue> {
-    let mut root: HashMap<String, MetadataValue> = HashMap::with_capacity(128);
-
-    // Track path through nested structure as keys
-    let mut path: Vec<String> = Vec::new();
-
+fn parse_tab_metadata(data: &[u8], standardize: bool) -> (HashMap<String, MetadataValue>, Vec<String>) {
     // First, fix line wraparound (lines > 1023 chars are continued)
     let fixed_data = fix_line_wraparound(data);
     let text = bytes_to_str(&fixed_data);
 
-    for line in text.lines() {
-        let line_bytes = line.as_bytes();
-        if trim(line_bytes).is_empty() {
-            continue;
-        }
+    let mut builder = HeaderBuilder::new(standardize);
+    read_tab_header(&text, &mut builder);
+    builder.finish()
+}
 
-        // Count leading tabs for indent level
-        let indent_level = line_bytes.iter().take_while(|&&b| b == b'\t').count();
-        let line_content = &line[indent_level..];
-        let line_content = line_content.trim_end();
+/// Read a tab-delimited header into header events.
+/// This format uses indentation (tabs) to indicate nesting
+fn read_tab_header(text: &str, builder: &mut HeaderBuilder) {
+    // (line number, indent level, content) of each non-blank line
+    let lines: Vec<(usize, usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let indent = line.bytes().take_while(|&b| b == b'\t').count();
+            let content = line[indent..].trim();
+            (!content.is_empty()).then_some((i + 1, indent, content))
+        })
+        .collect();
 
-        if line_content.is_empty() {
-            continue;
-        }
+    // Tags with a closing tag anywhere are sections, as in Archive headers
+    let section_tags: std::collections::HashSet<&str> = lines
+        .iter()
+        .filter_map(|(_, _, content)| content.strip_prefix("</")?.strip_suffix('>'))
+        .collect();
 
-        // Adjust path to current indent level
-        path.truncate(indent_level);
-
-        // Parse the line
-        if let Some(colon_pos) = line_content.find(':') {
-            // Check for special SEC-DOCUMENT/SEC-HEADER format: <TAG>value : date
-            if line_content.starts_with("<SEC-DOCUMENT>")
-                || line_content.starts_with("<SEC-HEADER>")
-            {
-                if let Some((key, value)) = parse_sec_header_line(line_content) {
-                    let final_key = if standardize {
-                        standardize_key(&key).into_owned()
-                    } else {
-                        key
-                    };
-                    insert_at_path(&mut root, &path, final_key, MetadataValue::String(value));
-                }
-            } else {
-                // Normal KEY: value
-                let key = line_content[..colon_pos].trim();
-                let value = line_content[colon_pos + 1..].trim();
+    // Open sections, innermost last
+    let mut open: Vec<TabFrame> = Vec::new();
 
-                let final_key = if standardize {
-                    standardize_key(key).into_owned()
-                } else {
-                    key.to_string()
-                };
+    for (i, &(line_number, indent, content)) in lines.iter().enumerate() {
+        // Indented sections the line is no longer under are closed; tag
+        // sections stay open until their closing tag
+        while let Some(&TabFrame::Indented(key, level)) = open.last() {
+            if level < indent {
+                break;
+            }
+            builder.apply(HeaderEvent::Close(key));
+            open.pop();
+        }
 
-                if value.is_empty() {
-                    // Section start - add to path
-                    insert_at_path(
-                        &mut root,
-                        &path,
-                        final_key.clone(),
-                        MetadataValue::Object(HashMap::new()),
-                    );
-                    path.push(final_key);
+        let expected = match open.last() {
+            Some(TabFrame::Indented(_, level)) => level + 1,
+            _ => 0,
+        };
+        if indent > expected {
+            builder.diagnostic(format!(
+                "line {}: indented {} levels where {} were expected",
+                line_number, indent, expected
+            ));
+        }
+
+        if content.starts_with('<') {
+            // Tag format <KEY>value, including the <SEC-DOCUMENT>/<SEC-HEADER> lines
+            if let Some(gt_pos) = content.find('>') {
+                let key = &content[1..gt_pos];
+                let value = content[gt_pos + 1..].trim();
+
+                if let Some(closed) = key.strip_prefix('/') {
+                    // Closing tags of envelope fields such as </SEC-HEADER> are skipped
+                    if let Some(pos) = open.iter().rposition(|frame| *frame == TabFrame::Tagged(closed)) {
+                        // Close the sections still open inside it first
+                        for frame in open.drain(pos..).rev() {
+                            let (TabFrame::Indented(key, _) | TabFrame::Tagged(key)) = frame;
+                            builder.apply(HeaderEvent::Close(key));
+                        }
+                    }
+                } else if value.is_empty() && section_tags.contains(key) {
+                    builder.apply(HeaderEvent::Open(key));
+                    open.push(TabFrame::Tagged(key));
                 } else {
-                    // Regular value
-                    let final_value = if standardize {
-                        transform_value(key, value)
-                    } else {
-                        value.to_string()
-                    };
-                    insert_at_path(
-                        &mut root,
-                        &path,
-                        final_key,
-                        MetadataValue::String(final_value),
-                    );
+                    builder.apply(HeaderEvent::Field(key, value));
                 }
             }
-        } else if line_content.starts_with('<') && line_content.contains('>') {
-            // Tag format <KEY>value
-            if let Some(gt_pos) = line_content.find('>') {
-                let key = &line_content[1..gt_pos];
-                let value = line_content[gt_pos + 1..].trim();
-
-                // Skip closing tags
-                if key.starts_with('/') {
-                    continue;
-                }
+        } else if let Some((key, value)) = content.split_once(':') {
+            let key = key.trim();
+            let value = value.trim();
 
-                let final_key = if standardize {
-                    standardize_key(key).into_owned()
-                } else {
-                    key.to_string()
-                };
-                let final_value = if standardize {
-                    transform_value(key, value)
-                } else {
-                    value.to_string()
-                };
+            // An empty value starts a section only if something is nested
+            // under it; otherwise it is an empty field, as in Archive headers
+            let has_children = lines
+                .get(i + 1)
+                .is_some_and(|&(_, next_indent, _)| next_indent > indent);
 
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key,
-                    MetadataValue::String(final_value),
-                );
+            if !value.is_empty() {
+                builder.apply(HeaderEvent::Field(key, value));
+            } else if has_children {
+                builder.apply(HeaderEvent::Open(key));
+                open.push(TabFrame::Indented(key, indent));
+            } else {
+                builder.apply(HeaderEvent::Field(key, ""));
             }
         }
     }
+}
 
-    root
+/// A section open in a tab header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TabFrame<'a> {
+    /// `KEY:` section and the indent level of its line
+    Indented(&'a str, usize),
+    /// `<KEY>` section closed by `</KEY>`
+    Tagged(&'a str),
 }
 
 /// Parse archive format metadata (XML-like tags with explicit closing tags)
-/// Parse archive format metadata (XML-like tags with explicit closing tags)
-fn parse_archive_metadata(data: &[u8], standardize: bool) -> HashMap<String, MetadataValue> {
-    let mut root: HashMap<String, MetadataValue> = HashMap::with_capacity(128);
+fn parse_archive_metadata(data: &[u8], standardize: bool) -> (HashMap<String, MetadataValue>, Vec<String>) {
+    let keyvals = parse_archive_keyvals(data);
 
-    // Track path through nested structure
-    let mut path: Vec<String> = Vec::new();
+    let mut builder = HeaderBuilder::new(standardize);
+    read_archive_header(&keyvals, &mut builder);
+    builder.finish()
+}
 
-    // First pass: identify which tags are sections (have closing tags)
-    let keyvals = parse_archive_keyvals(data);
+/// Read archive-format key-value pairs into header events
+fn read_archive_header(keyvals: &[(&[u8], &[u8])], builder: &mut HeaderBuilder) {
+    // Tags with a closing tag anywhere are sections
     let section_tags: std::collections::HashSet<&[u8]> = keyvals
         .iter()
-        .filter_map(|(key, _)| {
-            if key.starts_with(b"/") {
-                Some(&key[1..])
-            } else {
-                None
-            }
-        })
+        .filter_map(|(key, _)| key.strip_prefix(b"/"))
         .collect();
 
-    // Second pass: build nested structure
-    for (key, value) in &keyvals {
-        // Skip SUBMISSION tag
-        if key == b"SUBMISSION" {
+    for &(key, value) in keyvals {
+        // The SUBMISSION wrapper is not part of the header tree
+        if key == b"SUBMISSION" || key == b"/SUBMISSION" {
             continue;
         }
 
-        if key.starts_with(b"/") {
-            // Closing tag - pop path
-            path.pop();
-            continue;
-        }
-
-        let key_str = bytes_to_str(key);
-        let value_str = bytes_to_str(value);
-
-        if standardize {
-            let final_key = standardize_key(&key_str).into_owned();
-
-
-            if !value.is_empty() {
-                // Has value - it's a field
-                let final_value = transform_value(&key_str, &value_str);
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key,
-                    MetadataValue::String(final_value),
-                );
-            } else if section_tags.contains(key) {
-                // Section - create nested object and add to path
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key.clone(),
-                    MetadataValue::Object(HashMap::new()),
-                );
-                path.push(final_key);
-            } else {
-                // Empty field
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key,
-                    MetadataValue::String(String::new()),
-                );
-            }
+        if let Some(closed) = key.strip_prefix(b"/") {
+            builder.apply(HeaderEvent::Close(&bytes_to_str(closed)));
+        } else if value.is_empty() && section_tags.contains(key) {
+            builder.apply(HeaderEvent::Open(&bytes_to_str(key)));
         } else {
-            let final_key = key_str.into_owned(); 
-
-            let final_value = value_str.into_owned();
-
-            if !value.is_empty() {
-                // Has value - it's a field
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key,
-                    MetadataValue::String(final_value),
-                );
-            } else if section_tags.contains(key) {
-                // Section - create nested object and add to path
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key.clone(),
-                    MetadataValue::Object(HashMap::new()),
-                );
-                path.push(final_key);
-            } else {
-                // Empty field
-                insert_at_path(
-                    &mut root,
-                    &path,
-                    final_key,
-                    MetadataValue::String(String::new()),
-                );
-            }
+            builder.apply(HeaderEvent::Field(&bytes_to_str(key), &bytes_to_str(value)));
         }
     }
-
-    root
 }
+
 /// Parse archive format into key-value pairs (borrowed slices)
 fn parse_archive_keyvals(data: &[u8]) -> Vec<(&[u8], &[u8])> {
     let mut keyvals = Vec::new();
@@ -479,82 +573,6 @@ fn find_tag_end(line: &[u8]) -> Option<usize> {
     None
 }
 
-/// Parse special SEC-DOCUMENT/SEC-HEADER line format
-fn parse_sec_header_line(line: &str) -> Option<(String, String)> {
-    // Format: <SEC-DOCUMENT>filename.txt : date
-    let gt_pos = line.find('>')?;
-    let tag_name = &line[1..gt_pos];
-
-    let rest = &line[gt_pos + 1..];
-
-    // Find " : " separator
-    if let Some(colon_pos) = rest.find(" : ") {
-        let filename = rest[..colon_pos].trim();
-        let date = rest[colon_pos + 3..].trim();
-        Some((tag_name.to_string(), format!("{} : {}", filename, date)))
-    } else {
-        Some((tag_name.to_string(), rest.trim().to_string()))
-    }
-}
-
-/// Navigate to path and insert value, handling duplicate keys by converting to lists
-fn insert_at_path(
-    root: &mut HashMap<String, MetadataValue>,
-    path: &[String],
-    key: String,
-    value: MetadataValue,
-) {
-    if path.is_empty() {
-        insert_or_append(root, key, value);
-        return;
-    }
-
-    // Navigate through the path
-    let mut current = root as *mut HashMap<String, MetadataValue>;
-
-    for path_key in path {
-        let current_ref = unsafe { &mut *current };
-
-        match current_ref.get_mut(path_key) {
-            Some(MetadataValue::Object(obj)) => {
-                current = obj as *mut _;
-            }
-            Some(MetadataValue::List(list)) => {
-                // Get the last object in the list
-                if let Some(MetadataValue::Object(obj)) = list.last_mut() {
-                    current = obj as *mut _;
-                } else {
-                    return; // Can't navigate further
-                }
-            }
-            _ => return, // Can't navigate further
-        }
-    }
-
-    let target = unsafe { &mut *current };
-    insert_or_append(target, key, value);
-}
-
-/// Insert value into map, converting to list if key exists
-fn insert_or_append(map: &mut HashMap<String, MetadataValue>, key: String, value: MetadataValue) {
-    if let Some(existing) = map.get_mut(&key) {
-        match existing {
-            MetadataValue::List(list) => {
-                list.push(value);
-            }
-            _ => {
-                let old = std::mem::replace(existing, MetadataValue::List(Vec::new()));
-                if let MetadataValue::List(list) = existing {
-                    list.push(old);
-                    list.push(value);
-                }
-            }
-        }
-    } else {
-        map.insert(key, value);
-    }
-}
-
 /// Clean document content: strip wrapper tags and fix line wraparound
 fn clean_document_content(content: &[u8], format: SubmissionFormat, is_binary: bool) -> Vec<u8> {
     let mut content = trim(content);
@@ -581,12 +599,7 @@ fn clean_document_content(content: &[u8], format: SubmissionFormat, is_binary: b
     };
 
     // Fix line wraparound for tab-delimited formats (non-binary)
-    if !is_binary
-        && matches!(
-            format,
-            SubmissionFormat::TabPrivacy | SubmissionFormat::TabDefault
-        )
-    {
+    if !is_binary && format.is_tab() {
         return fix_line_wraparound(content);
     }
 
@@ -637,47 +650,13 @@ fn fix_line_wraparound(data: &[u8]) -> Vec<u8> {
     result
 }
 
-/// Apply document type filter
-fn apply_filter(
-    doc_metas: Vec<DocumentMetadata>,
-    documents: Vec<Vec<u8>>,
-    options: &ParseOptions,
-) -> (Vec<DocumentMetadata>, Vec<Vec<u8>>) {
-    if options.filter_document_types.is_empty() {
-        return (doc_metas, documents);
-    }
-
-    let type_key = if options.standardize_metadata {
-        "type"
-    } else {
-        "TYPE"
-    };
-
-    let indices: Vec<usize> = doc_metas
-        .iter()
-        .enumerate()
-        .filter_map(|(i, meta)| {
-            meta.fields.get(type_key).and_then(|t| {
-                if options.filter_document_types.contains(t) {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-        })
-        .collect();
-
-    if options.keep_filtered_metadata {
-        // Keep all metadata, filter only documents
-        let filtered_docs: Vec<Vec<u8>> = indices.iter().map(|&i| documents[i].clone()).collect();
-        (doc_metas, filtered_docs)
-    } else {
-        // Filter both
-        let filtered_metas: Vec<DocumentMetadata> =
-            indices.iter().map(|&i| doc_metas[i].clone()).collect();
-        let filtered_docs: Vec<Vec<u8>> = indices.iter().map(|&i| documents[i].clone()).collect();
-        (filtered_metas, filtered_docs)
-    }
+/// Whether a document passes `filter_document_types` and the document filter
+fn keep_document(meta: &DocumentMetadata, raw_content: &[u8], options: &ParseOptions) -> bool {
+    let type_listed = options.filter_document_types.is_empty()
+        || meta
+            .doc_type()
+            .is_some_and(|t| options.filter_document_types.iter().any(|f| f == t));
+    type_listed && options.document_filter.accepts(meta, raw_content)
 }
 
 /// Find double newline (blank line separator)
@@ -685,9 +664,10 @@ fn find_double_newline(data: &[u8]) -> Option<usize> {
     memmem::find(data, b"\n\n")
 }
 
-/// Convert bytes to Cow<str>, borrowing if valid UTF-8
+/// Convert bytes to Cow<str>, borrowing if valid UTF-8. Header bytes that
+/// are not UTF-8 are Latin-1 or Windows-1252, never U+FFFD.
 fn bytes_to_str(data: &[u8]) -> Cow<'_, str> {
-    String::from_utf8_lossy(data)
+    Encoding::Utf8.decode(data)
 }
 
 /// Trim leading whitespace from byte slice
@@ -713,3 +693,242 @@ fn trim_end(data: &[u8]) -> &[u8] {
 fn trim(data: &[u8]) -> &[u8] {
     trim_end(trim_start(data))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            detect_format(b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----\nProc-Type: 2001,MIC-CLEAR"),
+            SubmissionFormat::TabPrivacy
+        );
+        assert_eq!(detect_format(b"<SEC-DOCUMENT>x.txt : 20010102"), SubmissionFormat::TabDefault);
+        assert_eq!(detect_format(b"\n<SEC-HEADER>x.hdr.sgml : 20010102"), SubmissionFormat::SecHeader);
+        assert_eq!(detect_format(b"<IMS-DOCUMENT>x.txt : 19940103"), SubmissionFormat::ImsDocument);
+        assert_eq!(detect_format(b"<ARTICLE> 5\n<MULTIPLIER> 1,000"), SubmissionFormat::FinancialDataSchedule);
+        assert_eq!(
+            detect_format(b"<SUBMISSION>\n<ACCESSION-NUMBER>0000000000-01-000001\n<TYPE>8-K"),
+            SubmissionFormat::Archive
+        );
+        assert_eq!(
+            detect_format(b"<SUBMISSION>\n<ACCESSION-NUMBER>0000000000-01-000001\n<TYPE>CORRESP"),
+            SubmissionFormat::Correspondence
+        );
+        assert_eq!(detect_format(b"plain text"), SubmissionFormat::Unknown);
+    }
+
+    #[test]
+    fn test_force_format() {
+        let data = b"<SUBMISSION>\n<TYPE>CORRESP\n<DOCUMENT>\n<TYPE>CORRESP\n<TEXT>\nhi\n</TEXT>\n</DOCUMENT>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert_eq!(parsed.format, SubmissionFormat::Correspondence);
+
+        let parsed = parse_sgml(data, ParseOptions::new().with_format(SubmissionFormat::Archive)).unwrap();
+        assert_eq!(parsed.format, SubmissionFormat::Archive);
+        assert_eq!(parsed.documents, vec![b"hi".to_vec()]);
+    }
+
+    #[test]
+    fn test_privacy_trailer_excluded() {
+        let data = b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----\n\
+Proc-Type: 2001,MIC-CLEAR\n\
+\n\
+<SEC-DOCUMENT>x.txt : 20100406\n\
+ACCESSION NUMBER:\t\t0001010412-10-000078\n\
+</SEC-HEADER>\n\
+<DOCUMENT>\n\
+<TYPE>10-K\n\
+<TEXT>\n\
+body\n\
+</TEXT>\n\
+</DOCUMENT>\n\
+</SEC-DOCUMENT>\n\
+-----END PRIVACY-ENHANCED MESSAGE-----\n";
+
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert_eq!(parsed.format, SubmissionFormat::TabPrivacy);
+        assert_eq!(parsed.documents, vec![b"body".to_vec()]);
+        assert_eq!(
+            parsed.metadata.accession_number().unwrap().to_string(),
+            "0001010412-10-000078"
+        );
+
+        let pem = parsed.privacy_enhanced_message.unwrap();
+        assert_eq!(pem.proc_type.as_deref(), Some("2001,MIC-CLEAR"));
+        assert!(pem.end_byte.is_some());
+    }
+
+    #[test]
+    fn test_privacy_message_split_at_envelope_body() {
+        // The line ending the envelope header holds a space, so it is not
+        // a `\n\n` pair
+        let data = b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----\n\
+Proc-Type: 2001,MIC-CLEAR\n \n\
+<SEC-DOCUMENT>x.txt : 20100406\n\
+ACCESSION NUMBER:\t\t0001010412-10-000078\n\
+</SEC-HEADER>\n\
+<DOCUMENT>\n<TYPE>10-K\n<TEXT>\nbody\n</TEXT>\n</DOCUMENT>\n\
+</SEC-DOCUMENT>\n\
+-----END PRIVACY-ENHANCED MESSAGE-----\n";
+
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert_eq!(parsed.format, SubmissionFormat::TabPrivacy);
+        assert!(matches!(
+            parsed.metadata.fields.get("privacy-enhanced-message"),
+            Some(MetadataValue::String(message))
+                if message == "-----BEGIN PRIVACY-ENHANCED MESSAGE-----\nProc-Type: 2001,MIC-CLEAR"
+        ));
+        assert_eq!(
+            parsed.metadata.accession_number().unwrap().to_string(),
+            "0001010412-10-000078"
+        );
+
+        // A forced format still drops the envelope
+        let parsed = parse_sgml(data, ParseOptions::new().with_format(SubmissionFormat::SecHeader)).unwrap();
+        assert!(parsed.privacy_enhanced_message.is_some());
+        assert_eq!(parsed.documents, vec![b"body".to_vec()]);
+    }
+
+    mod header_equivalence {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Header tree that can be written in either format
+        #[derive(Debug, Clone)]
+        enum Node {
+            Field(&'static str, String),
+            Section(&'static str, Vec<Node>),
+        }
+
+        fn key() -> impl Strategy<Value = &'static str> {
+            prop::sample::select(vec!["ALPHA", "BRAVO CHARLIE", "DELTA", "ECHO FOXTROT"])
+        }
+
+        fn value() -> impl Strategy<Value = String> {
+            "[A-Za-z0-9]([A-Za-z0-9 .,]{0,10}[A-Za-z0-9])?"
+        }
+
+        fn node() -> impl Strategy<Value = Node> {
+            let field = (key(), value()).prop_map(|(k, v)| Node::Field(k, v));
+            field.prop_recursive(3, 32, 4, |inner| {
+                prop_oneof![
+                    (key(), value()).prop_map(|(k, v)| Node::Field(k, v)),
+                    (key(), prop::collection::vec(inner, 1..4)).prop_map(|(k, c)| Node::Section(k, c)),
+                ]
+            })
+        }
+
+        fn write_tab(nodes: &[Node], depth: usize, out: &mut String) {
+            for node in nodes {
+                out.push_str(&"\t".repeat(depth));
+                match node {
+                    Node::Field(key, value) => out.push_str(&format!("{}:\t\t{}\n", key, value)),
+                    Node::Section(key, children) => {
+                        out.push_str(&format!("{}:\n", key));
+                        write_tab(children, depth + 1, out);
+                    }
+                }
+            }
+        }
+
+        fn write_archive(nodes: &[Node], out: &mut String) {
+            for node in nodes {
+                match node {
+                    Node::Field(key, value) => {
+                        out.push_str(&format!("<{}>{}\n", key.replace(' ', "-"), value))
+                    }
+                    Node::Section(key, children) => {
+                        let tag = key.replace(' ', "-");
+                        out.push_str(&format!("<{}>\n", tag));
+                        write_archive(children, out);
+                        out.push_str(&format!("</{}>\n", tag));
+                    }
+                }
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn tab_and_archive_headers_match(nodes in prop::collection::vec(node(), 1..6)) {
+                let mut tab = String::new();
+                write_tab(&nodes, 0, &mut tab);
+                let mut archive = String::new();
+                write_archive(&nodes, &mut archive);
+
+                let (tab_fields, tab_diagnostics) = parse_tab_metadata(tab.as_bytes(), true);
+                let (archive_fields, archive_diagnostics) = parse_archive_metadata(archive.as_bytes(), true);
+
+                prop_assert_eq!(tab_fields, archive_fields);
+                prop_assert!(tab_diagnostics.is_empty());
+                prop_assert!(archive_diagnostics.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_unmatched_closing_tag_reported() {
+        let data = b"<SUBMISSION>\n<TYPE>8-K\n</FILER>\n<FILER>\n<CIK>1\n</FILER>\n<DOCUMENT>\n<TYPE>8-K\n<TEXT>\nhi\n</TEXT>\n</DOCUMENT>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.metadata.fields["filer"].as_object().unwrap()["cik"].as_str(),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn test_closing_tag_closes_nested_sections() {
+        // CLASS-CONTRACT is still open when the indented </SERIES> arrives
+        let data = b"<SEC-HEADER>0000000000-01-000001.hdr.sgml : 20010102\n\
+ACCESSION NUMBER:\t0000000000-01-000001\n\
+<SERIES>\n\
+CLASS-CONTRACT:\n\
+\tCLASS-CONTRACT-ID:\tC000001\n\
+\t</SERIES>\n\
+FILED AS OF DATE:\t20010102\n\
+</SEC-HEADER>\n";
+        let parsed = parse_sgml(data, ParseOptions::new().with_format(SubmissionFormat::SecHeader)).unwrap();
+        let fields = &parsed.metadata.fields;
+        assert_eq!(fields["filing-date"].as_str(), Some("20010102"));
+        let class = fields["series"].as_object().unwrap()["class-contract"].as_object().unwrap();
+        assert_eq!(class["class-contract-id"].as_str(), Some("C000001"));
+        assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+    }
+
+    /// Tab-only envelope fields with no Archive counterpart
+    const TAB_ENVELOPE_KEYS: &[&str] = &["sec-document", "sec-header", "privacy-enhanced-message"];
+
+    #[test]
+    fn test_conformance_corpus() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/conformance");
+        let mut pairs = 0;
+
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let tab_path = entry.unwrap().path();
+            let name = tab_path.file_name().unwrap().to_string_lossy().into_owned();
+            let Some(stem) = name.strip_suffix(".tab.txt") else {
+                continue;
+            };
+            let archive_path = dir.join(format!("{}.archive.txt", stem));
+
+            let tab = parse_sgml_file(&tab_path, ParseOptions::new()).unwrap();
+            let archive = parse_sgml_file(&archive_path, ParseOptions::new()).unwrap();
+            assert!(tab.format.is_tab(), "{}", name);
+            assert_eq!(archive.format, SubmissionFormat::Archive, "{}", name);
+            assert!(tab.diagnostics.is_empty(), "{}: {:?}", name, tab.diagnostics);
+            assert!(archive.diagnostics.is_empty(), "{}: {:?}", name, archive.diagnostics);
+
+            let mut tab_fields = tab.metadata.fields;
+            for key in TAB_ENVELOPE_KEYS {
+                tab_fields.remove(*key);
+            }
+            assert_eq!(tab_fields, archive.metadata.fields, "{}", stem);
+            assert_eq!(tab.documents, archive.documents, "{}", stem);
+            pairs += 1;
+        }
+
+        assert!(pairs > 0);
+    }
+}
@@ -0,0 +1,130 @@
+//! Privacy-enhanced message (RFC 1421) envelope parsing
+//!
+//! Older EDGAR submissions wrap the whole filing in a PEM envelope:
+//! a `-----BEGIN PRIVACY-ENHANCED MESSAGE-----` line, a block of
+//! `Name: value` header fields, the SGML body, and a closing
+//! `-----END PRIVACY-ENHANCED MESSAGE-----` trailer.
+
+use memchr::memmem;
+use serde::{Deserialize, Serialize};
+
+const PEM_BEGIN: &[u8] = b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----";
+const PEM_END: &[u8] = b"-----END PRIVACY-ENHANCED MESSAGE-----";
+
+/// Parsed privacy-enhanced message envelope
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyEnhancedMessage {
+    pub proc_type: Option<String>,
+    pub originator_name: Option<String>,
+    pub originator_key_asymmetric: Option<String>,
+    pub mic_info: Option<String>,
+    /// All header fields in order, continuation lines joined
+    pub fields: Vec<(String, String)>,
+    /// Byte offset of the BEGIN line
+    pub begin_byte: usize,
+    /// Byte offset where the enclosed SGML body starts
+    pub body_start_byte: usize,
+    /// Byte offset of the END trailer, if present
+    pub end_byte: Option<usize>,
+}
+
+impl PrivacyEnhancedMessage {
+    /// Look up a header field by name (case-insensitive)
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse the PEM envelope of a submission, if it has one
+pub fn parse_privacy_enhanced_message(data: &[u8]) -> Option<PrivacyEnhancedMessage> {
+    let begin_byte = data
+        .iter()
+        .position(|&b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'))?;
+    if !data[begin_byte..].starts_with(PEM_BEGIN) {
+        return None;
+    }
+
+    let mut pem = PrivacyEnhancedMessage {
+        begin_byte,
+        ..Default::default()
+    };
+
+    // Header fields run from the line after BEGIN to the first blank line
+    let mut pos = begin_byte + PEM_BEGIN.len();
+    pos += memchr::memchr(b'\n', &data[pos..]).map(|p| p + 1).unwrap_or(data.len() - pos);
+
+    while pos < data.len() {
+        let line_end = memchr::memchr(b'\n', &data[pos..])
+            .map(|p| pos + p)
+            .unwrap_or(data.len());
+        let line = String::from_utf8_lossy(&data[pos..line_end]);
+        let line = line.trim_end_matches('\r');
+        pos = (line_end + 1).min(data.len());
+
+        if line.trim().is_empty() {
+            break;
+        }
+
+        if line.starts_with(' ') || line.starts_with('\t') {
+            // Continuation of the previous field
+            if let Some((_, value)) = pem.fields.last_mut() {
+                value.push_str(line.trim());
+            }
+        } else if let Some(colon) = line.find(':') {
+            pem.fields.push((
+                line[..colon].trim().to_string(),
+                line[colon + 1..].trim().to_string(),
+            ));
+        }
+    }
+
+    pem.body_start_byte = pos;
+    pem.end_byte = memmem::rfind(&data[pos..], PEM_END).map(|p| pos + p);
+
+    pem.proc_type = pem.field("Proc-Type").map(String::from);
+    pem.originator_name = pem.field("Originator-Name").map(String::from);
+    pem.originator_key_asymmetric = pem.field("Originator-Key-Asymmetric").map(String::from);
+    pem.mic_info = pem.field("MIC-Info").map(String::from);
+
+    Some(pem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_privacy_enhanced_message() {
+        let data = b"-----BEGIN PRIVACY-ENHANCED MESSAGE-----\n\
+Proc-Type: 2001,MIC-CLEAR\n\
+Originator-Name: webmaster@www.sec.gov\n\
+Originator-Key-Asymmetric:\n \
+MFgwCgYEVQgBAQICAf8DSgAwRwJAW2sNKK9AVtBzYZmr6aGjlWyK3XmZv3dTINen\n \
+TWSM7vrzLADbmYQaionwg5sDW3P6oaM5D3tdezXMm7z1T+B+twIDAQAB\n\
+MIC-Info: RSA-MD5,RSA,\n \
+KmvU5Z5qk7fjtVUrbmvI373H1DIabgMfcRnAJHYQgap1oAWNDbShgwT7/+uA9Hn7\n\
+\n\
+<SEC-DOCUMENT>x.txt : 20100406\n\
+</SEC-DOCUMENT>\n\
+-----END PRIVACY-ENHANCED MESSAGE-----\n";
+
+        let pem = parse_privacy_enhanced_message(data).unwrap();
+        assert_eq!(pem.proc_type.as_deref(), Some("2001,MIC-CLEAR"));
+        assert_eq!(pem.originator_name.as_deref(), Some("webmaster@www.sec.gov"));
+        assert_eq!(
+            pem.originator_key_asymmetric.as_deref(),
+            Some("MFgwCgYEVQgBAQICAf8DSgAwRwJAW2sNKK9AVtBzYZmr6aGjlWyK3XmZv3dTINenTWSM7vrzLADbmYQaionwg5sDW3P6oaM5D3tdezXMm7z1T+B+twIDAQAB")
+        );
+        assert_eq!(
+            pem.mic_info.as_deref(),
+            Some("RSA-MD5,RSA,KmvU5Z5qk7fjtVUrbmvI373H1DIabgMfcRnAJHYQgap1oAWNDbShgwT7/+uA9Hn7")
+        );
+        assert!(data[pem.body_start_byte..].starts_with(b"<SEC-DOCUMENT>"));
+        assert!(data[pem.end_byte.unwrap()..].starts_with(PEM_END));
+
+        assert!(parse_privacy_enhanced_message(b"<SEC-DOCUMENT>x.txt").is_none());
+    }
+}
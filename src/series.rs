@@ -0,0 +1,193 @@
+//! Typed view of investment company series and classes
+//!
+//! Fund filings list the series and share classes (contracts) they cover
+//! under `SERIES-AND-CLASSES-CONTRACTS-DATA`, split into existing series,
+//! newly registered series and classes, and mergers.
+
+use crate::entity::{section_list, text};
+use crate::ids::Cik;
+use crate::types::MetadataValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Series and classes covered by a fund filing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SeriesAndClasses {
+    pub existing: Vec<Series>,
+    /// Series registered by this filing
+    pub new_series: Vec<Series>,
+    /// Existing series gaining classes registered by this filing
+    pub new_classes: Vec<Series>,
+    pub mergers: Vec<Merger>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Series {
+    /// CIK of the registrant the series belongs to
+    pub owner_cik: Option<Cik>,
+    /// Series identifier (`S000006027`)
+    pub series_id: Option<String>,
+    pub series_name: Option<String>,
+    pub classes: Vec<ClassContract>,
+}
+
+/// A share class or contract of a series
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClassContract {
+    /// Class identifier (`C000016568`)
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub ticker: Option<String>,
+}
+
+/// Series acquired by another series
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Merger {
+    pub acquiring: Vec<Series>,
+    pub target: Vec<Series>,
+}
+
+impl SeriesAndClasses {
+    /// Read the `series-and-classes-contracts-data` section
+    pub fn from_section(section: &HashMap<String, MetadataValue>) -> Self {
+        let mut result = SeriesAndClasses::default();
+
+        for existing in section_list(section, "existing-series-and-classes-contracts") {
+            result.existing.extend(series_in(existing, "series", None));
+        }
+
+        for new in section_list(section, "new-series-and-classes-contracts") {
+            let owner_cik = cik(new, "owner-cik");
+            result.new_series.extend(series_in(new, "new-series", owner_cik));
+            for classes in section_list(new, "new-classes-contracts") {
+                result.new_classes.extend(series_in(classes, "series", owner_cik));
+            }
+        }
+        for classes in section_list(section, "new-classes-contracts") {
+            result.new_classes.extend(series_in(classes, "series", None));
+        }
+
+        for mergers in section_list(section, "merger-series-and-classes-contracts") {
+            for merger in section_list(mergers, "merger") {
+                result.mergers.push(Merger {
+                    acquiring: merger_party(merger, "acquiring-data"),
+                    target: merger_party(merger, "target-data"),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// Series listed under `key`, with `owner_cik` as the owner when a series doesn't name one
+fn series_in(
+    section: &HashMap<String, MetadataValue>,
+    key: &str,
+    owner_cik: Option<Cik>,
+) -> Vec<Series> {
+    section_list(section, key)
+        .into_iter()
+        .map(|series| Series {
+            owner_cik: cik(series, "owner-cik").or(owner_cik),
+            series_id: text(series, "series-id"),
+            series_name: text(series, "series-name"),
+            classes: section_list(series, "class-contract")
+                .into_iter()
+                .map(|class| ClassContract {
+                    id: text(class, "class-contract-id"),
+                    name: text(class, "class-contract-name"),
+                    ticker: text(class, "class-contract-ticker-symbol"),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn merger_party(merger: &HashMap<String, MetadataValue>, key: &str) -> Vec<Series> {
+    section_list(merger, key)
+        .into_iter()
+        .flat_map(|party| series_in(party, "series", cik(party, "cik")))
+        .collect()
+}
+
+fn cik(section: &HashMap<String, MetadataValue>, key: &str) -> Option<Cik> {
+    text(section, key).and_then(|s| s.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_sgml, ParseOptions};
+
+    const SERIES_DATA: &str = "<SERIES-AND-CLASSES-CONTRACTS-DATA>
+<EXISTING-SERIES-AND-CLASSES-CONTRACTS>
+<SERIES>
+<OWNER-CIK>0000061397
+<SERIES-ID>S000006027
+<SERIES-NAME>Magellan Fund
+<CLASS-CONTRACT>
+<CLASS-CONTRACT-ID>C000016568
+<CLASS-CONTRACT-NAME>Magellan Fund
+<CLASS-CONTRACT-TICKER-SYMBOL>FMAGX
+</CLASS-CONTRACT>
+<CLASS-CONTRACT>
+<CLASS-CONTRACT-ID>C000016569
+<CLASS-CONTRACT-NAME>Class K
+</CLASS-CONTRACT>
+</SERIES>
+</EXISTING-SERIES-AND-CLASSES-CONTRACTS>
+<MERGER-SERIES-AND-CLASSES-CONTRACTS>
+<MERGER>
+<ACQUIRING-DATA>
+<CIK>0000061397
+<SERIES>
+<SERIES-ID>S000006027
+</SERIES>
+</ACQUIRING-DATA>
+<TARGET-DATA>
+<CIK>0000061398
+<SERIES>
+<SERIES-ID>S000006099
+</SERIES>
+</TARGET-DATA>
+</MERGER>
+</MERGER-SERIES-AND-CLASSES-CONTRACTS>
+</SERIES-AND-CLASSES-CONTRACTS-DATA>
+";
+
+    #[test]
+    fn test_series_and_classes() {
+        let tab = format!(
+            "<SEC-HEADER>0000061397-24-000001.hdr.sgml : 20240102\n\
+ACCESSION NUMBER:\t\t0000061397-24-000001\n\
+FILER:\n\
+\tCOMPANY DATA:\n\
+\t\tCENTRAL INDEX KEY:\t\t\t0000061397\n\
+{}</SEC-HEADER>\n",
+            SERIES_DATA
+        );
+        let archive = format!("<SUBMISSION>\n<ACCESSION-NUMBER>0000061397-24-000001\n{}</SUBMISSION>\n", SERIES_DATA);
+
+        for data in [tab, archive] {
+            let parsed = parse_sgml(data.as_bytes(), ParseOptions::new()).unwrap();
+            assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+
+            let series = parsed.metadata.series_and_classes().unwrap();
+            assert_eq!(series.existing.len(), 1);
+            let magellan = &series.existing[0];
+            assert_eq!(magellan.owner_cik.unwrap().value(), 61397);
+            assert_eq!(magellan.series_id.as_deref(), Some("S000006027"));
+            assert_eq!(magellan.classes.len(), 2);
+            assert_eq!(magellan.classes[0].ticker.as_deref(), Some("FMAGX"));
+            assert_eq!(magellan.classes[1].ticker, None);
+
+            let merger = &series.mergers[0];
+            assert_eq!(merger.target[0].owner_cik.unwrap().value(), 61398);
+            assert_eq!(merger.target[0].series_id.as_deref(), Some("S000006099"));
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! Plain-text rendering of filing documents
+//!
+//! HTML documents are rendered to readable text: scripts, styles and inline
+//! XBRL headers are dropped, block elements start new lines, paragraphs are
+//! separated by a blank line and table rows become ` | `-separated lines.
+//! Pre-2001 text documents keep their layout; only the SGML markup EDGAR
+//! added to them (`<PAGE>`, `<S>`, `<C>`, `<FN>`, ...) is normalized away.
+//! Windows-1252 and Latin-1 content is decoded rather than replaced.
+
+use crate::encoding::decode_text;
+use crate::types::{DocumentMetadata, ParsedSubmission};
+
+/// Separator between the cells of a rendered table row
+pub const CELL_SEPARATOR: &str = " | ";
+
+/// Elements whose content is never rendered
+const SKIPPED: &[&str] = &["script", "style", "head", "title", "ix:header", "noscript"];
+
+/// Elements that end a paragraph
+const PARAGRAPHS: &[&str] = &[
+    "p", "h1", "h2", "h3", "h4", "h5", "h6", "table", "blockquote", "ul", "ol", "dl", "pre", "hr",
+];
+
+/// Elements that end a line
+const LINES: &[&str] = &[
+    "div", "li", "dt", "dd", "center", "section", "article", "header", "footer", "address", "caption",
+];
+
+/// Render a document as text, choosing by filename and content between the
+/// HTML and the legacy text renderer. `None` for binary and XML documents.
+pub fn document_to_text(meta: &DocumentMetadata, content: &[u8]) -> Option<String> {
+    let extension = meta
+        .filename()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    let head = String::from_utf8_lossy(&content[..content.len().min(1024)]).to_ascii_lowercase();
+
+    match extension.as_deref() {
+        Some("htm" | "html") => Some(html_to_text(content)),
+        Some("txt") | None if head.contains("<html") => Some(html_to_text(content)),
+        Some("txt") | None if !head.trim_start().starts_with("<?xml") && !head.contains('\0') => {
+            Some(normalize_legacy_text(content))
+        }
+        _ => None,
+    }
+}
+
+impl ParsedSubmission {
+    /// Text of every kept HTML or plain-text document
+    pub fn documents_as_text(&self) -> impl Iterator<Item = (&DocumentMetadata, String)> {
+        self.documents_with_metadata()
+            .filter_map(|(meta, content)| Some((meta, document_to_text(meta, content)?)))
+    }
+}
+
+/// Render an HTML document as plain text
+pub fn html_to_text(html: &[u8]) -> String {
+    let (html, _) = decode_text(html);
+    let mut writer = TextWriter::default();
+    let mut rest: &str = &html;
+
+    while let Some(lt) = rest.find('<') {
+        writer.text(&rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        let Some((tag, after)) = read_tag(rest) else {
+            // A lone `<` in text
+            writer.text("<");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = after;
+
+        if !tag.closing && SKIPPED.contains(&tag.name.as_str()) {
+            rest = skip_element(rest, &tag.name);
+            continue;
+        }
+        writer.tag(&tag);
+    }
+    writer.text(rest);
+
+    writer.finish()
+}
+
+/// Remove the SGML markup EDGAR added to text documents, keeping the layout.
+///
+/// `<PAGE>` lines become blank lines, `<S>` and `<C>` column markers are
+/// replaced by spaces so columns stay aligned, table, caption and footnote
+/// tags are dropped and `<F1>` footnote references become `(1)`, padded to
+/// the width of the tag.
+pub fn normalize_legacy_text(text: &[u8]) -> String {
+    let (text, _) = decode_text(text);
+    let mut lines = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let upper = trimmed.to_ascii_uppercase();
+        if upper.starts_with("<PAGE>") {
+            lines.push(String::new());
+            continue;
+        }
+        if matches!(
+            upper.as_str(),
+            "<TABLE>" | "</TABLE>" | "<CAPTION>" | "</CAPTION>" | "<FN>" | "</FN>"
+        ) {
+            continue;
+        }
+        let normalized = normalize_legacy_line(line);
+        // Lines holding only column markers vanish rather than become blank
+        if !normalized.is_empty() || trimmed.is_empty() {
+            lines.push(normalized);
+        }
+    }
+
+    collapse_blank_lines(&lines.join("\n"))
+}
+
+fn normalize_legacy_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+        let tag = rest
+            .find('>')
+            .map(|gt| &rest[1..gt])
+            .filter(|tag| tag.len() <= 8 && tag.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'/'));
+
+        match tag.map(|tag| tag.to_ascii_uppercase()) {
+            Some(tag) if tag == "S" || tag == "C" => out.push_str(&" ".repeat(tag.len() + 2)),
+            Some(tag) if tag.len() > 1 && tag.starts_with('F') && tag[1..].bytes().all(|b| b.is_ascii_digit()) => {
+                out.push_str(&format!("{:<width$}", format!("({})", &tag[1..]), width = tag.len() + 2));
+            }
+            Some(tag) if matches!(tag.as_str(), "FN" | "/FN" | "CAPTION" | "/CAPTION" | "TABLE" | "/TABLE" | "R" | "/R") => {}
+            _ => {
+                out.push('<');
+                rest = &rest[1..];
+                continue;
+            }
+        }
+        rest = &rest[rest.find('>').unwrap_or(0) + 1..];
+    }
+    out.push_str(rest);
+    out.trim_end().to_string()
+}
+
+struct Tag {
+    /// Lowercase name
+    name: String,
+    closing: bool,
+}
+
+/// Read a tag starting at `<`, returning it and the text after its `>`
+fn read_tag(s: &str) -> Option<(Tag, &str)> {
+    let body = &s[1..];
+    let (closing, body) = match body.strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, body),
+    };
+    if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let name_len = body
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(body.len());
+    let name = body[..name_len].to_ascii_lowercase();
+
+    // Find the closing `>`, skipping over quoted attribute values
+    let mut quote = None;
+    for (i, c) in body.char_indices().skip(name_len) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some((Tag { name, closing }, &body[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Text after the closing tag of `name`, or nothing if it is never closed
+fn skip_element<'a>(s: &'a str, name: &str) -> &'a str {
+    let closing = s.match_indices("</").map(|(pos, _)| pos).find(|&pos| {
+        s.as_bytes()[pos + 2..]
+            .get(..name.len())
+            .is_some_and(|tag| tag.eq_ignore_ascii_case(name.as_bytes()))
+    });
+    match closing {
+        Some(pos) => s[pos..].find('>').map_or("", |end| &s[pos + end + 1..]),
+        None => "",
+    }
+}
+
+/// Accumulates rendered text, collapsing whitespace and buffering table rows
+#[derive(Default)]
+struct TextWriter {
+    out: String,
+    /// Cells of the table row being rendered
+    row: Option<Vec<String>>,
+    pending_space: bool,
+    /// Depth of open `<pre>` elements
+    pre: usize,
+}
+
+impl TextWriter {
+    fn target(&mut self) -> &mut String {
+        match self.row.as_mut() {
+            Some(cells) => {
+                if cells.is_empty() {
+                    cells.push(String::new());
+                }
+                cells.last_mut().unwrap()
+            }
+            None => &mut self.out,
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        if raw.is_empty() {
+            return;
+        }
+        let text = decode_entities(raw);
+        if self.pre > 0 && self.row.is_none() {
+            self.out.push_str(&text);
+            return;
+        }
+
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.pending_space = true;
+                continue;
+            }
+            let pending = std::mem::take(&mut self.pending_space);
+            let target = self.target();
+            if pending && !target.is_empty() && !target.ends_with('\n') {
+                target.push(' ');
+            }
+            target.push(c);
+        }
+    }
+
+    fn tag(&mut self, tag: &Tag) {
+        let name = tag.name.as_str();
+        match name {
+            "br" => {
+                if let Some(cells) = self.row.as_mut() {
+                    if let Some(cell) = cells.last_mut() {
+                        cell.push(' ');
+                    }
+                } else {
+                    self.out.push('\n');
+                }
+                self.pending_space = false;
+            }
+            "tr" => {
+                self.end_row();
+                if !tag.closing {
+                    self.line_break(1);
+                    self.row = Some(Vec::new());
+                }
+            }
+            "td" | "th" if !tag.closing => {
+                if let Some(cells) = self.row.as_mut() {
+                    cells.push(String::new());
+                }
+                self.pending_space = false;
+            }
+            "pre" => {
+                if tag.closing {
+                    self.pre = self.pre.saturating_sub(1);
+                } else {
+                    self.pre += 1;
+                }
+                self.line_break(2);
+            }
+            "table" => {
+                self.end_row();
+                self.line_break(2);
+            }
+            // Block elements inside a cell only separate words
+            _ if self.row.is_some() && (PARAGRAPHS.contains(&name) || LINES.contains(&name)) => {
+                self.pending_space = true;
+            }
+            _ if self.row.is_some() => {}
+            _ if PARAGRAPHS.contains(&name) => self.line_break(2),
+            _ if LINES.contains(&name) => self.line_break(1),
+            _ => {}
+        }
+    }
+
+    /// Write the buffered row as one line of non-empty cells
+    fn end_row(&mut self) {
+        if let Some(cells) = self.row.take() {
+            let cells: Vec<&str> = cells.iter().map(|c| c.trim()).filter(|c| !c.is_empty()).collect();
+            if !cells.is_empty() {
+                self.out.push_str(&cells.join(CELL_SEPARATOR));
+                self.line_break(1);
+            }
+        }
+        self.pending_space = false;
+    }
+
+    /// End the current line with at least `count` newlines
+    fn line_break(&mut self, count: usize) {
+        self.pending_space = false;
+        if self.out.is_empty() {
+            return;
+        }
+        let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+        for _ in existing..count {
+            self.out.push('\n');
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.end_row();
+        collapse_blank_lines(&self.out)
+    }
+}
+
+/// Trim line ends and reduce runs of blank lines to one
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank = false;
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Decode character references; unknown entities are left as written
+fn decode_entities(text: &str) -> std::borrow::Cow<'_, str> {
+    if !text.contains('&') {
+        return text.into();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((entity(&rest[1..end + 1])?, end + 2)));
+        match decoded {
+            Some((c, len)) => {
+                if c != '\u{AD}' {
+                    out.push(c);
+                }
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.into()
+}
+
+fn entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code).map(|c| if c == '\u{A0}' { ' ' } else { c });
+    }
+
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" | "ensp" | "emsp" | "thinsp" => ' ',
+        "shy" => '\u{AD}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "bull" => '•',
+        "middot" => '·',
+        "hellip" => '…',
+        "sect" => '§',
+        "para" => '¶',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "cent" => '¢',
+        "pound" => '£',
+        "euro" => '€',
+        "yen" => '¥',
+        "frac14" => '¼',
+        "frac12" => '½',
+        "frac34" => '¾',
+        "times" => '×',
+        "divide" => '÷',
+        "plusmn" => '±',
+        "dagger" => '†',
+        "Dagger" => '‡',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    #[test]
+    fn test_html_to_text() {
+        let html = br#"<html><head><title>10-K</title><style>p { color: red }</style></head>
+<body>
+<script type="text/javascript">var x = "<p>";</script>
+<div style="display:none"><ix:header><xbrli:context id="c-1">2023-09-30</xbrli:context></ix:header></div>
+<p>Item&#160;1A.&nbsp;&nbsp;Risk   Factors</p>
+<p>The Company&#8217;s results &amp; outlook<br>may vary.</p>
+<!-- <p>hidden</p> -->
+<table>
+<tr><td>Net sales</td><td></td><td>$</td><td>383,285</td></tr>
+<tr><td><p>Cost of</p><p>sales</p></td><td>$</td><td>214,137</td></tr>
+</table>
+<p>Done &unknown; 1 < 2</p>
+</body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Item 1A. Risk Factors\n\n\
+The Company\u{2019}s results & outlook\nmay vary.\n\n\
+Net sales | $ | 383,285\n\
+Cost of sales | $ | 214,137\n\n\
+Done &unknown; 1 < 2"
+        );
+    }
+
+    #[test]
+    fn test_normalize_legacy_text() {
+        let text = b"ITEM 8. FINANCIAL STATEMENTS\n\
+<TABLE>\n\
+<CAPTION>\n\
+                              1996        1995\n\
+</CAPTION>\n\
+<S>                           <C>         <C>\n\
+Net sales<F1>                 $1,234      $1,100\n\
+</TABLE>\n\
+<FN>\n\
+(1) Restated.\n\
+</FN>\n\
+\n\
+\n\
+<PAGE>   12\n\
+Continued text\n";
+
+        assert_eq!(
+            normalize_legacy_text(text),
+            "ITEM 8. FINANCIAL STATEMENTS\n\
+                              1996        1995\n\
+Net sales(1)                  $1,234      $1,100\n\
+(1) Restated.\n\
+\n\
+Continued text"
+        );
+    }
+
+    #[test]
+    fn test_documents_as_text() {
+        let data = b"<SUBMISSION>\n<TYPE>10-K\n\
+<DOCUMENT>\n<TYPE>10-K\n<SEQUENCE>1\n<FILENAME>form10k.htm\n<TEXT>\n<html><body><p>Annual report</p></body></html>\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-27\n<SEQUENCE>2\n<TEXT>\n<PAGE>\nFinancial data\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-101.INS\n<SEQUENCE>3\n<FILENAME>x.xml\n<TEXT>\n<XBRL>\n<?xml version=\"1.0\"?><xbrl/>\n</XBRL>\n</TEXT>\n</DOCUMENT>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+
+        let texts: Vec<(Option<&str>, String)> = parsed
+            .documents_as_text()
+            .map(|(meta, text)| (meta.doc_type(), text))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (Some("10-K"), "Annual report".to_string()),
+                (Some("EX-27"), "Financial data".to_string()),
+            ]
+        );
+    }
+}
@@ -1,5 +1,12 @@
 //! Type definitions for parsed SGML data
 
+use crate::encoding::Encoding;
+use crate::entity::{section_list, text_list, Entity};
+use crate::filter::DocumentFilter;
+use crate::ids::AccessionNumber;
+use crate::items::form_8k_item_number;
+use crate::pem::PrivacyEnhancedMessage;
+use crate::series::SeriesAndClasses;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -7,9 +14,35 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum SubmissionFormat {
+    /// Tab header wrapped in a `-----BEGIN PRIVACY-ENHANCED MESSAGE-----` envelope
     TabPrivacy,
+    /// Tab header starting with `<SEC-DOCUMENT>`
     TabDefault,
+    /// Header-only file starting with `<SEC-HEADER>` (e.g. `.hdr.sgml`)
+    SecHeader,
+    /// Early EDGAR tab header starting with `<IMS-DOCUMENT>` or `<IMS-HEADER>`
+    ImsDocument,
+    /// Tagged dissemination header starting with `<SUBMISSION>`
     Archive,
+    /// Tagged dissemination header of a `CORRESP` submission
+    Correspondence,
+    /// Bare EX-27 financial data schedule (`<ARTICLE>`, `<MULTIPLIER>`, ...)
+    FinancialDataSchedule,
+    /// Content that matches none of the known layouts
+    Unknown,
+}
+
+impl SubmissionFormat {
+    /// Whether the header uses the indented `KEY: value` layout
+    pub fn is_tab(&self) -> bool {
+        matches!(
+            self,
+            SubmissionFormat::TabPrivacy
+                | SubmissionFormat::TabDefault
+                | SubmissionFormat::SecHeader
+                | SubmissionFormat::ImsDocument
+        )
+    }
 }
 
 /// A metadata value: string, list, or nested object
@@ -64,27 +97,52 @@ pub struct DocumentMetadata {
     #[serde(flatten)]
     pub fields: HashMap<String, String>,
 
+    /// Decoded content size; 0 for filtered-out documents, which are
+    /// never decoded
     #[serde(rename = "secsgml_size_bytes")]
     pub size_bytes: usize,
 
+    /// Raw (still encoded) content size, recorded for filtered-out
+    /// documents only
+    #[serde(rename = "secsgml_raw_size_bytes", default, skip_serializing_if = "Option::is_none")]
+    pub raw_size_bytes: Option<usize>,
+
     #[serde(rename = "secsgml_start_byte", skip_serializing_if = "Option::is_none")]
     pub start_byte: Option<String>,
 
     #[serde(rename = "secsgml_end_byte", skip_serializing_if = "Option::is_none")]
     pub end_byte: Option<String>,
+
+    /// Detected content encoding, recorded for text documents when
+    /// `ParseOptions::detect_encoding` is set
+    #[serde(rename = "secsgml_encoding", default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Encoding>,
+
+    /// Position of the content in `ParsedSubmission::documents`, `None`
+    /// when the content was filtered out
+    #[serde(skip)]
+    pub content_index: Option<usize>,
 }
 
 impl DocumentMetadata {
+    /// Field value under its standardized or original key
+    fn field(&self, standard: &str, original: &str) -> Option<&str> {
+        self.fields
+            .get(standard)
+            .or_else(|| self.fields.get(original))
+            .map(|s| s.as_str())
+    }
+
     pub fn doc_type(&self) -> Option<&str> {
-        self.fields.get("type").map(|s| s.as_str())
+        self.field("type", "TYPE")
     }
 
     pub fn filename(&self) -> Option<&str> {
-        self.fields.get("filename").map(|s| s.as_str())
+        self.field("filename", "FILENAME")
     }
 
     pub fn sequence(&self) -> Option<&str> {
-        self.fields.get("sequence").map(|s| s.as_str())
+        self.field("sequence", "SEQUENCE")
     }
 }
 
@@ -97,15 +155,110 @@ pub struct SubmissionMetadata {
     pub documents: Vec<DocumentMetadata>,
 }
 
+impl SubmissionMetadata {
+    /// Top-level string field, trying each key spelling in turn
+    fn top_level_str(&self, keys: &[&str]) -> Option<&str> {
+        keys.iter()
+            .find_map(|key| self.fields.get(*key))
+            .and_then(MetadataValue::as_str)
+    }
+
+    /// Accession number, whether or not keys were standardized
+    pub fn accession_number(&self) -> Option<AccessionNumber> {
+        self.top_level_str(&["accession-number", "ACCESSION NUMBER", "ACCESSION-NUMBER"])?
+            .parse()
+            .ok()
+    }
+
+    /// Form type of the submission (`10-K`, `8-K`, ...)
+    pub fn submission_type(&self) -> Option<&str> {
+        self.top_level_str(&["type", "TYPE", "CONFORMED SUBMISSION TYPE"])
+    }
+
+    /// Item numbers the header lists for a current report (`2.02`, `9.01`).
+    ///
+    /// Archive headers give numbers; tab headers give descriptions under
+    /// `ITEM INFORMATION`, which are translated to numbers where known.
+    pub fn items(&self) -> Vec<String> {
+        let mut items = text_list(&self.fields, "items");
+        items.extend(
+            text_list(&self.fields, "item-information")
+                .iter()
+                .filter_map(|description| form_8k_item_number(description))
+                .map(str::to_string),
+        );
+        items
+    }
+
+    fn entities(&self, section: &str) -> Vec<Entity> {
+        section_list(&self.fields, section)
+            .into_iter()
+            .map(Entity::from_section)
+            .collect()
+    }
+
+    pub fn filers(&self) -> Vec<Entity> {
+        self.entities("filer")
+    }
+
+    /// Insiders reporting on Forms 3, 4 and 5
+    pub fn reporting_owners(&self) -> Vec<Entity> {
+        self.entities("reporting-owner")
+    }
+
+    /// Companies whose securities a Form 3, 4 or 5 reports on
+    pub fn issuers(&self) -> Vec<Entity> {
+        self.entities("issuer")
+    }
+
+    /// Companies a schedule 13D/G or tender offer is about
+    pub fn subject_companies(&self) -> Vec<Entity> {
+        self.entities("subject-company")
+    }
+
+    /// Persons filing a schedule 13D/G or tender offer
+    pub fn filed_by(&self) -> Vec<Entity> {
+        self.entities("filed-by")
+    }
+
+    /// Names listed under `GROUP MEMBERS`
+    pub fn group_members(&self) -> Vec<String> {
+        text_list(&self.fields, "group-members")
+    }
+
+    /// Series and classes of a fund filing, if the header lists any
+    pub fn series_and_classes(&self) -> Option<SeriesAndClasses> {
+        section_list(&self.fields, "series-and-classes-contracts-data")
+            .into_iter()
+            .next()
+            .map(SeriesAndClasses::from_section)
+    }
+}
+
 /// Options for parsing
 #[derive(Debug, Clone, Default)]
 pub struct ParseOptions {
     /// Filter to specific document types (empty = all)
     pub filter_document_types: Vec<String>,
+    /// Patterns, sequence, extension, content type and size conditions a
+    /// document must also meet. Documents it rejects are never decoded.
+    pub document_filter: DocumentFilter,
     /// Keep metadata for filtered-out documents
     pub keep_filtered_metadata: bool,
     /// Standardize keys to lowercase kebab-case
     pub standardize_metadata: bool,
+    /// Skip format detection and parse as this format. A PEM envelope is
+    /// still parsed and stripped.
+    pub force_format: Option<SubmissionFormat>,
+    /// Rewrite header dates as ISO-8601 and split `filename : date` composites
+    pub normalize_dates: bool,
+    /// Add descriptions next to SIC and state/country codes
+    pub enrich_codes: bool,
+    /// Always emit repeatable sections (`filer`, `reporting-owner`, ...) as lists
+    pub normalize_lists: bool,
+    /// Detect the encoding of each kept text document and record it in
+    /// `DocumentMetadata::encoding`. Contents stay as raw bytes.
+    pub detect_encoding: bool,
 }
 
 impl ParseOptions {
@@ -127,6 +280,21 @@ impl ParseOptions {
         self.filter_document_types = types;
         self
     }
+
+    pub fn with_document_filter(mut self, filter: DocumentFilter) -> Self {
+        self.document_filter = filter;
+        self
+    }
+
+    pub fn with_format(mut self, format: SubmissionFormat) -> Self {
+        self.force_format = Some(format);
+        self
+    }
+
+    pub fn with_encoding_detection(mut self) -> Self {
+        self.detect_encoding = true;
+        self
+    }
 }
 /// Result of parsing an SGML submission
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,4 +303,31 @@ pub struct ParsedSubmission {
     #[serde(skip)]
     pub documents: Vec<Vec<u8>>,
     pub format: SubmissionFormat,
-}
\ No newline at end of file
+    /// Parsed PEM envelope of submissions wrapped in one, whatever their
+    /// detected or forced format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy_enhanced_message: Option<PrivacyEnhancedMessage>,
+    /// Problems found while building the header tree (unmatched closing
+    /// tags, inconsistent indentation). Nothing is dropped because of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<String>,
+}
+
+impl ParsedSubmission {
+    /// Documents whose content was kept, paired with their metadata
+    pub fn documents_with_metadata(&self) -> impl Iterator<Item = (&DocumentMetadata, &[u8])> {
+        self.metadata.documents.iter().filter_map(|meta| {
+            let content = self.documents.get(meta.content_index?)?;
+            Some((meta, content.as_slice()))
+        })
+    }
+
+    /// Kept documents whose type is one of `types`
+    pub fn documents_of_type<'a>(
+        &'a self,
+        types: &'a [&str],
+    ) -> impl Iterator<Item = (&'a DocumentMetadata, &'a [u8])> + 'a {
+        self.documents_with_metadata()
+            .filter(|(meta, _)| meta.doc_type().is_some_and(|t| types.contains(&t)))
+    }
+}
@@ -28,13 +28,22 @@ impl std::error::Error for UuDecodeError {}
 /// 
 /// Valid characters are in range [32, 96] (space through backtick).
 pub fn a2b_uu(data: &[u8]) -> Result<Vec<u8>, UuDecodeError> {
+    let mut bin_data = Vec::new();
+    a2b_uu_into(data, &mut bin_data)?;
+    Ok(bin_data)
+}
+
+/// Decode a line of uuencoded data, appending to `bin_data`.
+///
+/// On error, bytes of the line may already have been appended.
+fn a2b_uu_into(data: &[u8], bin_data: &mut Vec<u8>) -> Result<(), UuDecodeError> {
     if data.is_empty() {
-        return Ok(Vec::new());
+        return Ok(());
     }
 
     let bin_len = ((data[0].wrapping_sub(b' ')) & 0o77) as usize;
     
-    let mut bin_data = Vec::with_capacity(bin_len);
+    bin_data.reserve(bin_len);
     let mut leftbits = 0;
     let mut leftchar: u32 = 0;
     let mut remaining = bin_len;
@@ -51,7 +60,7 @@ pub fn a2b_uu(data: &[u8]) -> Result<Vec<u8>, UuDecodeError> {
             if byte == b'\n' || byte == b'\r' {
                 0u8
             } else {
-                if byte < b' ' || byte > (b' ' + 64) {
+                if !(b' '..=b' ' + 64).contains(&byte) {
                     return Err(UuDecodeError::IllegalChar);
                 }
                 (byte - b' ') & 0o77
@@ -74,7 +83,7 @@ pub fn a2b_uu(data: &[u8]) -> Result<Vec<u8>, UuDecodeError> {
     
     // Trailing garbage check...
     let bytes_processed = bin_len;
-    let chars_needed = (bytes_processed * 8 + 5) / 6;
+    let chars_needed = (bytes_processed * 8).div_ceil(6);
     let start_check = 1 + chars_needed;
     
     if start_check < data.len() {
@@ -85,7 +94,7 @@ pub fn a2b_uu(data: &[u8]) -> Result<Vec<u8>, UuDecodeError> {
         }
     }
     
-    Ok(bin_data)
+    Ok(())
 }
 /// Check if content is UU-encoded by looking for "begin XXX filename" pattern
 /// in the first two lines where XXX is a 3-digit Unix permission mode.
@@ -132,63 +141,50 @@ fn check_begin_line(line: &[u8]) -> bool {
 pub fn decode_uuencoded(content: &[u8]) -> Vec<u8> {
     let mut result = Vec::with_capacity(content.len() * 3 / 4);
     
-    // Convert to string for line processing (UU is ASCII-safe)
-    let text: String = match std::str::from_utf8(content) {
-        Ok(s) => s.to_string(),
-        Err(_) => String::from_utf8_lossy(content).into_owned(),
-    };
-    
-    let mut lines = text.lines();
+    // UU is ASCII, so lines are processed as bytes without a UTF-8 copy
+    let mut lines = content.split(|&b| b == b'\n');
     
     // Find the "begin" line
-    let mut found_begin = false;
-    for line in lines.by_ref() {
-        if line.starts_with("begin") {
-            found_begin = true;
-            break;
-        }
-    }
-    
-    if !found_begin {
+    if !lines.by_ref().any(|line| line.starts_with(b"begin")) {
         return result;
     }
     
-    // Process data lines
+    // Process data lines, reusing one buffer for the cleaned line
+    let mut clean_line = Vec::with_capacity(64);
     for line in lines {
-        let stripped = line.trim_end_matches('\r');
+        let end = line.iter().rposition(|&b| b != b'\r').map_or(0, |i| i + 1);
+        let stripped = &line[..end];
         
-        if stripped.is_empty() || stripped == "end" {
+        if stripped.is_empty() || stripped == b"end" {
             break;
         }
         
-        if let Some(decoded) = decode_uu_line(stripped) {
-            result.extend_from_slice(&decoded);
-        }
+        clean_line.clear();
+        clean_line.extend(stripped.iter().copied().filter(|b| (32..=95).contains(b)));  // Changed from 96 to 95
+        decode_uu_line(&clean_line, &mut result);
     }
     
     result
 }
 
-/// Decode a single UU-encoded line (matching Python's fallback behavior)
-
-fn decode_uu_line(line: &str) -> Option<Vec<u8>> {
-    let clean_line: String = line.chars()
-        .filter(|&c| c as u32 >= 32 && c as u32 <= 95)  // Changed from 96 to 95
-        .collect();
-    
-    if clean_line.is_empty() {
-        return None;
-    }
+/// Decode a single UU-encoded line (matching Python's fallback behavior),
+/// appending to `out`. Lines that fail to decode add nothing.
+fn decode_uu_line(clean_line: &[u8], out: &mut Vec<u8>) {
+    let Some(&length_char) = clean_line.first() else {
+        return;
+    };
     
     // Calculate how many encoded characters we need
-    let length_char = clean_line.chars().next()?;
     let expected_bytes = ((length_char as u32 - 32) & 63) as usize;
     let nbytes = (expected_bytes * 4 + 5) / 3;  // Number of encoded chars needed
     
     // Only pass the required number of characters to a2b_uu
-    let truncated_line: String = clean_line.chars().take(nbytes + 1).collect();  // +1 for length char
+    let truncated_line = &clean_line[..clean_line.len().min(nbytes + 1)];  // +1 for length char
     
-    a2b_uu(truncated_line.as_bytes()).ok()
+    let mark = out.len();
+    if a2b_uu_into(truncated_line, out).is_err() {
+        out.truncate(mark);
+    }
 }
 
 /// Trim leading whitespace from byte slice
@@ -201,4 +197,21 @@ fn trim_start(data: &[u8]) -> &[u8] {
         }
     }
     &data[start..]
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_uuencoded() {
+        let encoded = b"begin 644 hello.txt\r\n-2&5L;&\\L('=O<FQD(0  \r\n`\r\nend\r\n";
+        assert!(is_uuencoded(encoded));
+        assert_eq!(decode_uuencoded(encoded), b"Hello, world!");
+
+        // A line with trailing garbage is skipped, the rest still decodes
+        let corrupt = b"begin 644 hello.txt\n-2&5L;&\\L('=O<FQD(0  \n#86)C__X\n#86)C\nend\n";
+        assert_eq!(decode_uuencoded(corrupt), b"Hello, world!abc");
+
+        assert!(decode_uuencoded(b"no begin line").is_empty());
+    }
+}
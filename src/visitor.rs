@@ -0,0 +1,80 @@
+//! Streaming access to a submission's documents
+//!
+//! [`visit_sgml`](crate::visit_sgml) hands each kept document to a
+//! [`SubmissionVisitor`] as soon as it is decoded and drops it afterwards,
+//! so only one document is held at a time. [`parse_sgml`](crate::parse_sgml)
+//! is the same walk with a visitor that keeps everything.
+
+use crate::types::{DocumentMetadata, SubmissionMetadata};
+use std::ops::ControlFlow;
+
+/// Callbacks for [`visit_sgml`](crate::visit_sgml) and
+/// [`visit_sgml_file`](crate::visit_sgml_file)
+pub trait SubmissionVisitor {
+    /// Called once with the parsed header, before any document. Its
+    /// `documents` list is still empty.
+    fn on_header(&mut self, _metadata: &SubmissionMetadata) {}
+
+    /// Called for each document the filters keep, in submission order,
+    /// with its decoded content. `size_bytes` is set; `content_index` is
+    /// not. Return [`ControlFlow::Break`] to stop without decoding the
+    /// remaining documents.
+    fn on_document(&mut self, metadata: &DocumentMetadata, content: &[u8]) -> ControlFlow<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{visit_sgml, DocumentFilter, ParseOptions};
+
+    const SUBMISSION: &[u8] = b"<SUBMISSION>\n<TYPE>8-K\n\
+<DOCUMENT>\n<TYPE>8-K\n<SEQUENCE>1\n<FILENAME>form8-k.htm\n<TEXT>\nCurrent report\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-99.1\n<SEQUENCE>2\n<FILENAME>ex99-1.htm\n<TEXT>\nPress release\n</TEXT>\n</DOCUMENT>\n\
+<DOCUMENT>\n<TYPE>EX-99.2\n<SEQUENCE>3\n<FILENAME>ex99-2.htm\n<TEXT>\nSlides\n</TEXT>\n</DOCUMENT>\n";
+
+    #[derive(Default)]
+    struct Recorder {
+        form: Option<String>,
+        seen: Vec<(String, usize)>,
+        stop_at: Option<&'static str>,
+    }
+
+    impl SubmissionVisitor for Recorder {
+        fn on_header(&mut self, metadata: &SubmissionMetadata) {
+            self.form = metadata.submission_type().map(str::to_string);
+        }
+
+        fn on_document(&mut self, metadata: &DocumentMetadata, content: &[u8]) -> ControlFlow<()> {
+            let doc_type = metadata.doc_type().unwrap_or_default();
+            assert_eq!(metadata.size_bytes, content.len());
+            self.seen.push((doc_type.to_string(), content.len()));
+            if self.stop_at == Some(doc_type) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_visit_all_documents() {
+        let mut recorder = Recorder::default();
+        visit_sgml(SUBMISSION, ParseOptions::new(), &mut recorder).unwrap();
+        assert_eq!(recorder.form.as_deref(), Some("8-K"));
+        assert_eq!(
+            recorder.seen,
+            vec![("8-K".to_string(), 14), ("EX-99.1".to_string(), 13), ("EX-99.2".to_string(), 6)]
+        );
+    }
+
+    #[test]
+    fn test_visit_stops_early() {
+        let mut recorder = Recorder {
+            stop_at: Some("EX-99.1"),
+            ..Default::default()
+        };
+        let options = ParseOptions::new().with_document_filter(DocumentFilter::types(["EX-99*"]));
+        visit_sgml(SUBMISSION, options, &mut recorder).unwrap();
+        assert_eq!(recorder.seen, vec![("EX-99.1".to_string(), 13)]);
+    }
+}
@@ -0,0 +1,510 @@
+//! XBRL instance and inline XBRL fact extraction
+//!
+//! Facts come from `EX-101.INS` instance documents and from inline XBRL
+//! (iXBRL) HTML documents. A fact refers to its context and unit by id, and
+//! in a multi-document iXBRL set those may be defined in another document,
+//! so contexts and units are collected from every XBRL document of a
+//! submission before facts are resolved.
+
+use crate::error::{ParseError, Result};
+use crate::types::ParsedSubmission;
+use crate::xml::{child, children, number, parse_xml, text_at, xml_text};
+use memchr::memmem;
+use roxmltree::Node;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Document type of an XBRL instance
+pub const XBRL_INSTANCE: &str = "EX-101.INS";
+
+const XBRLI_NS: &str = "http://www.xbrl.org/2003/instance";
+const INLINE_NS: &str = "http://www.xbrl.org/2013/inlineXBRL";
+
+/// A reported value with its context and unit resolved
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Fact {
+    /// Qualified concept name (`us-gaap:Revenues`)
+    pub concept: String,
+    pub context_ref: Option<String>,
+    pub period: Option<Period>,
+    /// Entity identifier of the context, usually the CIK
+    pub entity: Option<String>,
+    /// Explicit and typed members of the context, by dimension
+    pub dimensions: BTreeMap<String, String>,
+    pub unit_ref: Option<String>,
+    /// Unit measures (`iso4217:USD`, `iso4217:USD/xbrli:shares`)
+    pub unit: Option<String>,
+    /// `-6`, `INF`, ...
+    pub decimals: Option<String>,
+    /// Value as reported; inline numbers have scale and sign applied.
+    /// `None` for nil facts.
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Period {
+    Instant { date: String },
+    Duration { start: String, end: String },
+    Forever,
+}
+
+impl Fact {
+    /// Numeric value of the fact
+    pub fn number(&self) -> Option<f64> {
+        self.value.as_deref().and_then(number)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Context {
+    entity: Option<String>,
+    period: Option<Period>,
+    dimensions: BTreeMap<String, String>,
+}
+
+/// Contexts, units and unresolved facts read from one or more documents
+#[derive(Debug, Default)]
+struct FactSet {
+    contexts: HashMap<String, Context>,
+    units: HashMap<String, String>,
+    facts: Vec<Fact>,
+}
+
+impl FactSet {
+    fn read(&mut self, data: &[u8]) -> Result<()> {
+        let text = xml_text(data);
+        let xml = parse_xml(&text)?;
+        let root = xml.root_element();
+
+        let inline = is_inline(data);
+        let instance = root.tag_name().name() == "xbrl" && root.tag_name().namespace() == Some(XBRLI_NS);
+        if !inline && !instance {
+            return Err(ParseError::XmlError(format!(
+                "expected an XBRL instance, found {}",
+                root.tag_name().name()
+            )));
+        }
+
+        for node in root.descendants().filter(|n| n.is_element()) {
+            match (node.tag_name().namespace(), node.tag_name().name()) {
+                (Some(XBRLI_NS), "context") => {
+                    if let Some(id) = node.attribute("id") {
+                        self.contexts.insert(id.to_string(), context(node));
+                    }
+                }
+                (Some(XBRLI_NS), "unit") => {
+                    if let Some(id) = node.attribute("id") {
+                        self.units.insert(id.to_string(), unit(node));
+                    }
+                }
+                (Some(INLINE_NS), "nonFraction" | "nonNumeric") => {
+                    self.facts.extend(inline_fact(node));
+                }
+                _ if !inline && node.parent_element() == Some(root) => {
+                    self.facts.extend(instance_fact(node));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill in each fact's period, entity, dimensions and unit
+    fn resolve(self) -> Vec<Fact> {
+        let FactSet { contexts, units, mut facts } = self;
+        for fact in &mut facts {
+            if let Some(context) = fact.context_ref.as_ref().and_then(|id| contexts.get(id)) {
+                fact.period = context.period.clone();
+                fact.entity = context.entity.clone();
+                fact.dimensions = context.dimensions.clone();
+            }
+            fact.unit = fact.unit_ref.as_ref().and_then(|id| units.get(id)).cloned();
+        }
+        facts
+    }
+}
+
+/// Extract the facts of one instance or inline XBRL document
+pub fn parse_xbrl(data: &[u8]) -> Result<Vec<Fact>> {
+    let mut set = FactSet::default();
+    set.read(data)?;
+    Ok(set.resolve())
+}
+
+impl ParsedSubmission {
+    /// Facts of the submission's XBRL instance and inline XBRL documents.
+    ///
+    /// An `EX-101.INS` document that cannot be read is an error. Other
+    /// documents are only picked up by the inline XBRL namespace, so those
+    /// that are not well-formed XML, such as an HTML exhibit that merely
+    /// mentions the namespace, are skipped; use [`parse_xbrl`] to see why
+    /// one fails.
+    pub fn xbrl_facts(&self) -> Result<Vec<Fact>> {
+        let mut set = FactSet::default();
+        for (meta, content) in self.documents_with_metadata() {
+            if meta.doc_type() == Some(XBRL_INSTANCE) {
+                set.read(content)?;
+            } else if is_inline(content) {
+                // A failed read leaves the set unchanged
+                let _ = set.read(content);
+            }
+        }
+        Ok(set.resolve())
+    }
+}
+
+/// Whether a document declares the inline XBRL namespace
+fn is_inline(data: &[u8]) -> bool {
+    memmem::find(data, INLINE_NS.as_bytes()).is_some()
+}
+
+fn context(node: Node) -> Context {
+    let period = child(node, "period").and_then(|period| {
+        if let Some(date) = text_at(period, &["instant"]) {
+            Some(Period::Instant { date })
+        } else if child(period, "forever").is_some() {
+            Some(Period::Forever)
+        } else {
+            Some(Period::Duration {
+                start: text_at(period, &["startDate"])?,
+                end: text_at(period, &["endDate"])?,
+            })
+        }
+    });
+
+    let entity = child(node, "entity");
+    let dimensions = entity
+        .and_then(|entity| child(entity, "segment"))
+        .into_iter()
+        .chain(child(node, "scenario"))
+        .flat_map(|container| container.children().filter(|n| n.is_element()))
+        .filter_map(|member| {
+            let dimension = member.attribute("dimension")?;
+            Some((dimension.to_string(), text_at(member, &[]).unwrap_or_default()))
+        })
+        .collect();
+
+    Context {
+        entity: entity.and_then(|entity| text_at(entity, &["identifier"])),
+        period,
+        dimensions,
+    }
+}
+
+fn unit(node: Node) -> String {
+    let measures = |node: Option<Node>| {
+        node.map(|node| {
+            children(node, "measure")
+                .filter_map(|m| text_at(m, &[]))
+                .collect::<Vec<_>>()
+                .join("*")
+        })
+        .unwrap_or_default()
+    };
+
+    match child(node, "divide") {
+        Some(divide) => format!(
+            "{}/{}",
+            measures(child(divide, "unitNumerator")),
+            measures(child(divide, "unitDenominator"))
+        ),
+        None => measures(Some(node)),
+    }
+}
+
+/// A fact element of an instance document: any top-level element with a context
+fn instance_fact(node: Node) -> Option<Fact> {
+    let context_ref = node.attribute("contextRef")?;
+    let name = node.tag_name();
+    let concept = match name.namespace().and_then(|ns| node.lookup_prefix(ns)) {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name.name()),
+        _ => name.name().to_string(),
+    };
+
+    Some(Fact {
+        concept,
+        context_ref: Some(context_ref.to_string()),
+        unit_ref: node.attribute("unitRef").map(str::to_string),
+        decimals: node.attribute("decimals").map(str::to_string),
+        value: (!is_nil(node)).then(|| node.text().unwrap_or_default().trim().to_string()),
+        ..Default::default()
+    })
+}
+
+/// An `ix:nonFraction` or `ix:nonNumeric` element
+fn inline_fact(node: Node) -> Option<Fact> {
+    let concept = node.attribute("name")?;
+    let text = inline_text(node);
+
+    let value = if is_nil(node) {
+        None
+    } else if node.tag_name().name() == "nonFraction" {
+        Some(inline_number(node, &text))
+    } else {
+        Some(text.trim().to_string())
+    };
+
+    Some(Fact {
+        concept: concept.to_string(),
+        context_ref: node.attribute("contextRef").map(str::to_string),
+        unit_ref: node.attribute("unitRef").map(str::to_string),
+        decimals: node.attribute("decimals").map(str::to_string),
+        value,
+        ..Default::default()
+    })
+}
+
+/// Displayed number with the `format`, `scale` and `sign` attributes applied.
+/// Text that doesn't read as a number is returned as written.
+///
+/// The scale moves the decimal point in the digit string, so the value
+/// stays exactly as reported instead of passing through `f64`.
+fn inline_number(node: Node, text: &str) -> String {
+    let text = text.trim();
+    let format = node.attribute("format").unwrap_or_default();
+    let digits = if format.contains("zero") || format.contains("dash") {
+        "0".to_string()
+    } else if format.contains("comma-decimal") || format.contains("numcommadecimal") {
+        // 1.234,5
+        text.replace(['.', ' '], "").replace(',', ".")
+    } else {
+        text.replace([',', ' '], "")
+    };
+
+    let scale = node.attribute("scale").and_then(|s| s.parse::<i32>().ok()).unwrap_or(0);
+    let negative = node.attribute("sign") == Some("-");
+    scale_decimal(&digits, scale, negative).unwrap_or_else(|| text.to_string())
+}
+
+/// `digits` (`1234.5`) times 10^`scale`, negated when `negative`, as a
+/// decimal string without redundant zeros. `None` unless `digits` is an
+/// unsigned decimal number.
+fn scale_decimal(digits: &str, scale: i32, negative: bool) -> Option<String> {
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if int.is_empty() && frac.is_empty() || !(int.bytes().chain(frac.bytes())).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    // Place the decimal point `scale` digits further right
+    let all = format!("{}{}", int, frac);
+    let point = (int.len() as i64 + i64::from(scale)).clamp(-1000, 1000);
+    let (int, frac) = if point <= 0 {
+        (String::new(), format!("{}{}", "0".repeat(point.unsigned_abs() as usize), all))
+    } else if point as usize >= all.len() {
+        (format!("{}{}", all, "0".repeat(point as usize - all.len())), String::new())
+    } else {
+        let (int, frac) = all.split_at(point as usize);
+        (int.to_string(), frac.to_string())
+    };
+
+    let int = int.trim_start_matches('0');
+    let frac = frac.trim_end_matches('0');
+    let mut value = if int.is_empty() { "0".to_string() } else { int.to_string() };
+    if !frac.is_empty() {
+        value.push('.');
+        value.push_str(frac);
+    }
+    if negative && value != "0" {
+        value.insert(0, '-');
+    }
+    Some(value)
+}
+
+/// Text of an inline fact, leaving out `ix:exclude` content
+fn inline_text(node: Node) -> String {
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+        } else if child.is_element()
+            && !(child.tag_name().namespace() == Some(INLINE_NS) && child.tag_name().name() == "exclude")
+        {
+            text.push_str(&inline_text(child));
+        }
+    }
+    text
+}
+
+fn is_nil(node: Node) -> bool {
+    node.attribute(("http://www.w3.org/2001/XMLSchema-instance", "nil")) == Some("true")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sgml, ParseOptions};
+
+    const INSTANCE: &[u8] = br#"<?xml version="1.0" encoding="utf-8"?>
+<xbrli:xbrl xmlns:xbrli="http://www.xbrl.org/2003/instance"
+    xmlns:us-gaap="http://fasb.org/us-gaap/2023"
+    xmlns:dei="http://xbrl.sec.gov/dei/2023"
+    xmlns:xbrldi="http://xbrl.org/2006/xbrldi"
+    xmlns:iso4217="http://www.xbrl.org/2003/iso4217"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <xbrli:context id="FY2023">
+    <xbrli:entity>
+      <xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier>
+    </xbrli:entity>
+    <xbrli:period>
+      <xbrli:startDate>2022-09-25</xbrli:startDate>
+      <xbrli:endDate>2023-09-30</xbrli:endDate>
+    </xbrli:period>
+  </xbrli:context>
+  <xbrli:context id="FY2023_Products">
+    <xbrli:entity>
+      <xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier>
+      <xbrli:segment>
+        <xbrldi:explicitMember dimension="srt:ProductOrServiceAxis">us-gaap:ProductMember</xbrldi:explicitMember>
+      </xbrli:segment>
+    </xbrli:entity>
+    <xbrli:period>
+      <xbrli:instant>2023-09-30</xbrli:instant>
+    </xbrli:period>
+  </xbrli:context>
+  <xbrli:unit id="usd">
+    <xbrli:measure>iso4217:USD</xbrli:measure>
+  </xbrli:unit>
+  <xbrli:unit id="usdPerShare">
+    <xbrli:divide>
+      <xbrli:unitNumerator><xbrli:measure>iso4217:USD</xbrli:measure></xbrli:unitNumerator>
+      <xbrli:unitDenominator><xbrli:measure>xbrli:shares</xbrli:measure></xbrli:unitDenominator>
+    </xbrli:divide>
+  </xbrli:unit>
+  <dei:DocumentType contextRef="FY2023">10-K</dei:DocumentType>
+  <us-gaap:Revenues contextRef="FY2023" unitRef="usd" decimals="-6">383285000000</us-gaap:Revenues>
+  <us-gaap:Revenues contextRef="FY2023_Products" unitRef="usd" decimals="-6">298085000000</us-gaap:Revenues>
+  <us-gaap:EarningsPerShareBasic contextRef="FY2023" unitRef="usdPerShare" decimals="2">6.16</us-gaap:EarningsPerShareBasic>
+  <us-gaap:Goodwill contextRef="FY2023_Products" unitRef="usd" xsi:nil="true"/>
+</xbrli:xbrl>
+"#;
+
+    #[test]
+    fn test_instance_facts() {
+        let facts = parse_xbrl(INSTANCE).unwrap();
+        assert_eq!(facts.len(), 5);
+
+        assert_eq!(facts[0].concept, "dei:DocumentType");
+        assert_eq!(facts[0].value.as_deref(), Some("10-K"));
+        assert_eq!(facts[0].unit, None);
+
+        let revenues = &facts[1];
+        assert_eq!(revenues.concept, "us-gaap:Revenues");
+        assert_eq!(revenues.entity.as_deref(), Some("0000320193"));
+        assert_eq!(
+            revenues.period,
+            Some(Period::Duration {
+                start: "2022-09-25".to_string(),
+                end: "2023-09-30".to_string()
+            })
+        );
+        assert_eq!(revenues.unit.as_deref(), Some("iso4217:USD"));
+        assert_eq!(revenues.decimals.as_deref(), Some("-6"));
+        assert_eq!(revenues.number(), Some(383285000000.0));
+        assert!(revenues.dimensions.is_empty());
+
+        let products = &facts[2];
+        assert_eq!(products.period, Some(Period::Instant { date: "2023-09-30".to_string() }));
+        assert_eq!(
+            products.dimensions.get("srt:ProductOrServiceAxis").map(String::as_str),
+            Some("us-gaap:ProductMember")
+        );
+
+        assert_eq!(facts[3].unit.as_deref(), Some("iso4217:USD/xbrli:shares"));
+        assert_eq!(facts[4].value, None);
+    }
+
+    #[test]
+    fn test_inline_facts_across_documents() {
+        let data = br#"<SUBMISSION>
+<TYPE>10-K
+<DOCUMENT>
+<TYPE>10-K
+<SEQUENCE>1
+<FILENAME>form10-k.htm
+<TEXT>
+<XBRL>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:ix="http://www.xbrl.org/2013/inlineXBRL"
+    xmlns:xbrli="http://www.xbrl.org/2003/instance" xmlns:us-gaap="http://fasb.org/us-gaap/2023">
+<body>
+<div style="display:none"><ix:header><ix:resources>
+  <xbrli:context id="c-1">
+    <xbrli:entity><xbrli:identifier scheme="http://www.sec.gov/CIK">0000320193</xbrli:identifier></xbrli:entity>
+    <xbrli:period><xbrli:startDate>2022-09-25</xbrli:startDate><xbrli:endDate>2023-09-30</xbrli:endDate></xbrli:period>
+  </xbrli:context>
+  <xbrli:unit id="usd"><xbrli:measure>iso4217:USD</xbrli:measure></xbrli:unit>
+</ix:resources></ix:header></div>
+<p>Net sales were $<ix:nonFraction name="us-gaap:Revenues" contextRef="c-1" unitRef="usd" decimals="-6" scale="6" format="ixt:num-dot-decimal">383,285</ix:nonFraction> million.</p>
+</body>
+</html>
+</XBRL>
+</TEXT>
+</DOCUMENT>
+<DOCUMENT>
+<TYPE>EX-99.1
+<SEQUENCE>2
+<FILENAME>ex99-1.htm
+<TEXT>
+<XBRL>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:ix="http://www.xbrl.org/2013/inlineXBRL">
+<body>
+<p>Net loss of $<ix:nonFraction name="us-gaap:NetIncomeLoss" contextRef="c-1" unitRef="usd" decimals="0" sign="-" format="ixt:num-dot-decimal">1,500</ix:nonFraction>
+and <ix:nonFraction name="us-gaap:Goodwill" contextRef="c-1" unitRef="usd" decimals="0" format="ixt:fixed-zero">&#8212;</ix:nonFraction> goodwill.</p>
+<ix:nonNumeric name="us-gaap:Policy" contextRef="c-1">Revenue is recognized<ix:exclude> (page 3)</ix:exclude> on delivery.</ix:nonNumeric>
+</body>
+</html>
+</XBRL>
+</TEXT>
+</DOCUMENT>
+<DOCUMENT>
+<TYPE>EX-99.2
+<SEQUENCE>3
+<FILENAME>ex99-2.htm
+<TEXT>
+<html><body><p>Tagged with http://www.xbrl.org/2013/inlineXBRL<br></body></html>
+</TEXT>
+</DOCUMENT>
+</SUBMISSION>
+"#;
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        let facts = parsed.xbrl_facts().unwrap();
+        assert_eq!(facts.len(), 4);
+
+        assert_eq!(facts[0].concept, "us-gaap:Revenues");
+        assert_eq!(facts[0].value.as_deref(), Some("383285000000"));
+
+        // Context and unit defined in the first document
+        assert_eq!(facts[1].number(), Some(-1500.0));
+        assert_eq!(facts[1].entity.as_deref(), Some("0000320193"));
+        assert_eq!(facts[1].unit.as_deref(), Some("iso4217:USD"));
+
+        assert_eq!(facts[2].number(), Some(0.0));
+        assert_eq!(facts[3].value.as_deref(), Some("Revenue is recognized on delivery."));
+    }
+
+    #[test]
+    fn test_scale_decimal() {
+        assert_eq!(scale_decimal("0.07", 2, false).as_deref(), Some("7"));
+        assert_eq!(scale_decimal("383285", 6, false).as_deref(), Some("383285000000"));
+        assert_eq!(scale_decimal("1.5", -2, true).as_deref(), Some("-0.015"));
+        assert_eq!(scale_decimal("12.340", 0, false).as_deref(), Some("12.34"));
+        assert_eq!(scale_decimal("0.1", 1, false).as_deref(), Some("1"));
+        assert_eq!(scale_decimal("0", 3, true).as_deref(), Some("0"));
+        assert_eq!(scale_decimal("007", 0, false).as_deref(), Some("7"));
+        assert_eq!(scale_decimal("1e3", 0, false), None);
+        assert_eq!(scale_decimal("-5", 0, false), None);
+        assert_eq!(scale_decimal(".", 0, false), None);
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        assert!(parse_xbrl(b"<informationTable/>").is_err());
+
+        let data = b"<SUBMISSION>\n<TYPE>10-K\n\
+<DOCUMENT>\n<TYPE>EX-101.INS\n<SEQUENCE>2\n<FILENAME>abc-20231231.xml\n<TEXT>\n<XBRL>\n<xbrli:xbrl>\n</XBRL>\n</TEXT>\n</DOCUMENT>\n";
+        let parsed = parse_sgml(data, ParseOptions::new()).unwrap();
+        assert!(parsed.xbrl_facts().is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! Helpers shared by the XML document parsers
+
+use crate::error::{ParseError, Result};
+use roxmltree::{Document, Node, ParsingOptions};
+use std::borrow::Cow;
+
+/// Decode an XML document payload, skipping whitespace before the declaration
+pub(crate) fn xml_text(data: &[u8]) -> Cow<'_, str> {
+    let start = data.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[start..])
+}
+
+pub(crate) fn parse_xml(text: &str) -> Result<Document<'_>> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    Document::parse_with_options(text, options).map_err(|e| ParseError::XmlError(e.to_string()))
+}
+
+/// First child element with the given local name
+pub(crate) fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// Child elements with the given local name
+pub(crate) fn children<'a, 'input: 'a, 'n>(
+    node: Node<'a, 'input>,
+    name: &'n str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'n
+where
+    'a: 'n,
+{
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// Trimmed, non-empty text of the element at `path` below `node`
+pub(crate) fn text_at(node: Node<'_, '_>, path: &[&str]) -> Option<String> {
+    let mut node = node;
+    for name in path {
+        node = child(node, name)?;
+    }
+    let text: String = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Parse a number written with optional thousands separators
+pub(crate) fn number(text: &str) -> Option<f64> {
+    let cleaned: String = text.chars().filter(|&c| c != ',' && c != '$').collect();
+    cleaned.trim().parse().ok()
+}
+
+/// Parse `1`/`0` and `true`/`false` flags
+#[cfg(feature = "ownership")]
+pub(crate) fn flag(text: &str) -> bool {
+    matches!(text.trim().to_ascii_lowercase().as_str(), "1" | "true" | "y" | "yes")
+}